@@ -1,6 +1,9 @@
-use super::utils::parse_datetime_string_add_nanos_optionally;
+use super::tzdb::local_time_zone;
+use super::utils::{
+    add_span_with_overflow, get_month_overflow_from_string,
+    parse_datetime_string_add_nanos_optionally, parse_jiff_span_from_duration_string,
+};
 use crate::DtPlugin;
-use jiff::Span as JiffSpan;
 use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
 use nu_protocol::{
     Category, Example, LabeledError, Signature, Span as NuSpan, Spanned, SyntaxShape, Value,
@@ -21,6 +24,17 @@ impl SimplePluginCommand for DtAdd {
                 SyntaxShape::String,
                 "Duration to add to the provided in date and time",
             )
+            .named(
+                "overflow",
+                SyntaxShape::String,
+                "How to handle month and year arithmetic landing past the end of a month: clamp (default), roll or reject",
+                Some('o'),
+            )
+            .switch(
+                "preserve-end-of-month",
+                "Keep datetimes on the last day of a month on the last day of the resulting month",
+                Some('e'),
+            )
             .category(Category::Date)
     }
 
@@ -55,6 +69,20 @@ impl SimplePluginCommand for DtAdd {
                     "2017-09-08T00:00:00-05:00[America/Chicago]",
                 )),
             },
+            Example {
+                example: "'2024-01-31T00:00:00-06:00[America/Chicago]' | dt add 1m --overflow roll",
+                description: "Add 1 month to January 31st, rolling the extra days into March instead of clamping to February 29th",
+                result: Some(Value::test_string(
+                    "2024-03-02T00:00:00-06:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-04-30T00:00:00-05:00[America/Chicago]' | dt add 1m --preserve-end-of-month",
+                description: "Add 1 month to the last day of April, landing on the last day of May",
+                result: Some(Value::test_string(
+                    "2024-05-31T00:00:00-05:00[America/Chicago]",
+                )),
+            },
             Example {
                 example: "dt now | dt add 2w",
                 description: "Add jiff duration of 2 weeks to the provided dt command date in the local timezone",
@@ -71,71 +99,21 @@ impl SimplePluginCommand for DtAdd {
     fn run(
         &self,
         _plugin: &DtPlugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
         // From weirdan
         // If no timezone is specified, assume local tz. Provide a way to override that. Alternatively, reject dates without a timezone.
 
         let span: NuSpan = input.span();
         // TODO: Accomodate negative jiff spans like -P1d
-        let mut duration_string: Spanned<String> = call.req(0)?;
-
-        // The jiff span is roughly comparable to this
-        // P(\d+y)?(\d+m)?(\d+w)?(\d+d)?(T(\d+h)?(\d+m)?(\d+s)?)?
-        // P = date designator
-        // y = years
-        // m = months
-        // w = weeks
-        // d = days
-        // T = time designator
-        // h = hours
-        // m = minutes
-        // s = seconds
-        // .000 = milliseconds
-        // .000000 = microseconds
-        // .000000000 = nanoseconds
-
-        // is it negative
-        if (duration_string.item.starts_with('-')
-            || duration_string.item.starts_with('+'))
-            // is it longer than 2
-            && duration_string.item.len() > 2
-            // is the 2nd character not a P
-            && duration_string.item.chars().nth(1).unwrap() != 'P'
-        {
-            duration_string.item.insert(1, 'P');
-        } else if !duration_string.item.starts_with('P') {
-            // else if it doesn't start with P, add it because jiff's span parser requires it
-            duration_string.item.insert(0, 'P');
-        }
-
-        let jiff_span: JiffSpan = duration_string.item.parse().map_err(|err| {
-            LabeledError::new(format!("Error parsing duration: {err}"))
-                .with_label(
-                    format!("error parsing {:?} as a jiff span", duration_string.item),
-                    duration_string.span,
-                )
-                .with_help(
-                    r#"These are the valid component abbreviations:
-+ = positive
-- = negative
-P = date designator (inferred if you forget it)
-y = years
-m = months
-w = weeks
-d = days
-T = time designator
-h = hours
-m = minutes
-s = seconds
-.000 = milliseconds
-.000000 = microseconds
-.000000000 = nanoseconds
-"#,
-                )
-        })?;
+        let duration_string: Spanned<String> = call.req(0)?;
+        let overflow = get_month_overflow_from_string(call.get_flag("overflow")?)?;
+        let preserve_end_of_month = call.has_flag("preserve-end-of-month")?;
+
+        let jiff_span = parse_jiff_span_from_duration_string(duration_string)?;
 
         // eprintln!("Jiff span: {:?}", jiff_span);
 
@@ -152,12 +130,12 @@ s = seconds
 
                 // Check for just a date like 2017-08-25 being passed in. If it is, then the time will be 00:00:00+00:00
                 let mut rfc3399 = val.to_rfc3339();
-                if rfc3399.ends_with("T00:00:00+00:00") {
-                    if let Some(empty_time) = rfc3399.rfind("T00:00:00+00:00") {
-                        rfc3399 = rfc3399[0..empty_time].to_string();
-                    }
+                if rfc3399.ends_with("T00:00:00+00:00")
+                    && let Some(empty_time) = rfc3399.rfind("T00:00:00+00:00")
+                {
+                    rfc3399 = rfc3399[0..empty_time].to_string();
                 }
-                parse_datetime_string_add_nanos_optionally(&rfc3399, None, span, Some(jiff_span))?
+                parse_datetime_string_add_nanos_optionally(&rfc3399, None, span, None, &local_tz)?
             }
             Value::String { val, .. } => {
                 // eprintln!("String: {:?}", val);
@@ -167,7 +145,7 @@ s = seconds
                 //     span,
                 //     None,
                 // )?
-                parse_datetime_string_add_nanos_optionally(val, None, span, Some(jiff_span))?
+                parse_datetime_string_add_nanos_optionally(val, None, span, None, &local_tz)?
             }
             _ => {
                 return Err(LabeledError::new(
@@ -175,6 +153,8 @@ s = seconds
                 ));
             }
         };
+        let datetime =
+            add_span_with_overflow(&datetime, jiff_span, overflow, preserve_end_of_month)?;
         Ok(Value::string(datetime.to_string(), call.head))
     }
 }
@@ -191,3 +171,17 @@ fn test_examples() -> Result<(), nu_protocol::ShellError> {
 
     PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtAdd)
 }
+
+#[test]
+fn test_date_only_input_follows_session_tz() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    let result = PluginTest::new("dt", DtPlugin.into())?
+        .eval("$env.TZ = 'Asia/Kolkata'; '2024-08-25' | dt add 1day")?
+        .into_value(NuSpan::test_data())?;
+    assert_eq!(
+        result,
+        Value::test_string("2024-08-26T00:00:00+05:30[Asia/Kolkata]")
+    );
+    Ok(())
+}
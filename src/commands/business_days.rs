@@ -0,0 +1,351 @@
+use super::holidays::{HolidayCalendar, load_holiday_calendar};
+use super::tzdb::local_time_zone;
+use super::utils::{get_weekday_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{ToSpan, Zoned, civil, civil::Weekday};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+use std::{cell::RefCell, collections::BTreeMap};
+
+pub struct DtAddBusinessDays;
+
+impl SimplePluginCommand for DtAddBusinessDays {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt add-business-days"
+    }
+
+    fn signature(&self) -> Signature {
+        business_calendar_flags(Signature::build(self.name()).required(
+            "days",
+            SyntaxShape::Int,
+            "Number of business days to add, negative numbers go backwards.",
+        ))
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Add a number of business days to the provided date or datetime, keeping its time of day and time zone"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "workday", "weekday", "working", "sla"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-23T17:30:00-05:00[America/Chicago]' | dt add-business-days 3",
+                description: "Add 3 business days to a Friday, skipping the weekend",
+                result: Some(Value::test_string(
+                    "2024-08-28T17:30:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-12-23T09:00:00+00:00[UTC]' | dt add-business-days 2 --holidays [2024-12-25 2024-12-26]",
+                description: "Add 2 business days skipping inline holiday dates",
+                result: Some(Value::test_string("2024-12-27T09:00:00+00:00[UTC]")),
+            },
+            Example {
+                example: "'2024-08-22T10:00:00+03:00[Asia/Riyadh]' | dt add-business-days 1 --weekend [fri sat]",
+                description: "Add 1 business day with a Friday and Saturday weekend",
+                result: Some(Value::test_string("2024-08-25T10:00:00+03:00[Asia/Riyadh]")),
+            },
+            Example {
+                example: "'2026-12-24T09:00:00-05:00[America/New_York]' | dt add-business-days 1 --calendar nyse",
+                description: "Add 1 business day skipping the holidays of a holiday calendar",
+                result: Some(Value::test_string(
+                    "2026-12-28T09:00:00-05:00[America/New_York]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let days: i64 = call.req(0)?;
+        let calendar = get_business_calendar_from_call(engine, call)?;
+
+        let datetime = get_zoned_from_value(input, &local_tz)?;
+        let date = calendar.add_business_days(datetime.date(), days)?;
+        let result = with_date(&datetime, date)?;
+        Ok(Value::string(result.to_string(), call.head))
+    }
+}
+
+// Which days are worked. Weekends are a set of weekdays so masks like Friday and
+// Saturday work, holidays are individual dates plus the observed dates of an
+// optional holiday calendar.
+#[derive(Clone, Debug)]
+pub struct BusinessCalendar {
+    weekend: Vec<Weekday>,
+    holidays: Vec<civil::Date>,
+    holiday_calendar: Option<HolidayCalendar>,
+    // Observed holiday calendar dates by year, filled in as years are looked at
+    observed_by_year: RefCell<BTreeMap<i16, Vec<civil::Date>>>,
+}
+
+impl Default for BusinessCalendar {
+    fn default() -> Self {
+        BusinessCalendar {
+            weekend: vec![Weekday::Saturday, Weekday::Sunday],
+            holidays: vec![],
+            holiday_calendar: None,
+            observed_by_year: RefCell::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl BusinessCalendar {
+    pub fn new(
+        weekend: Vec<Weekday>,
+        mut holidays: Vec<civil::Date>,
+    ) -> Result<BusinessCalendar, LabeledError> {
+        if (0..7).all(|day| {
+            weekend
+                .iter()
+                .any(|weekday| weekday.to_monday_zero_offset() == day)
+        }) {
+            return Err(LabeledError::new(
+                "The weekend can't include every day of the week".to_string(),
+            ));
+        }
+        holidays.sort();
+        holidays.dedup();
+        Ok(BusinessCalendar {
+            weekend,
+            holidays,
+            ..BusinessCalendar::default()
+        })
+    }
+
+    pub fn with_holiday_calendar(mut self, holiday_calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = Some(holiday_calendar);
+        self.observed_by_year.borrow_mut().clear();
+        self
+    }
+
+    pub fn is_weekend(&self, date: civil::Date) -> bool {
+        self.weekend.contains(&date.weekday())
+    }
+
+    pub fn is_holiday(&self, date: civil::Date) -> bool {
+        if self.holidays.binary_search(&date).is_ok() {
+            return true;
+        }
+        let Some(holiday_calendar) = &self.holiday_calendar else {
+            return false;
+        };
+        self.observed_by_year
+            .borrow_mut()
+            .entry(date.year())
+            .or_insert_with(|| {
+                holiday_calendar
+                    .holidays_around(date)
+                    .into_iter()
+                    .map(|holiday| holiday.observed)
+                    .filter(|observed| observed.year() == date.year())
+                    .collect()
+            })
+            .contains(&date)
+    }
+
+    pub fn is_business_day(&self, date: civil::Date) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    // Step one day at a time counting only business days, like a spreadsheet's
+    // WORKDAY, so adding 1 to a Saturday lands on Monday and adding 0 changes nothing
+    pub fn add_business_days(
+        &self,
+        date: civil::Date,
+        days: i64,
+    ) -> Result<civil::Date, LabeledError> {
+        let step = if days < 0 { (-1).day() } else { 1.day() };
+        let mut date = date;
+        let mut remaining = days.unsigned_abs();
+        while remaining > 0 {
+            date = date
+                .checked_add(step)
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            if self.is_business_day(date) {
+                remaining -= 1;
+            }
+        }
+        Ok(date)
+    }
+
+    // Count the business days from start up to but not including end, negative when
+    // end is before start
+    pub fn count_business_days(&self, start: civil::Date, end: civil::Date) -> i64 {
+        let (from, to, sign) = if start <= end {
+            (start, end, 1)
+        } else {
+            (end, start, -1)
+        };
+        let count = from
+            .series(1.day())
+            .take_while(|date| *date < to)
+            .filter(|date| self.is_business_day(*date))
+            .count();
+        count as i64 * sign
+    }
+}
+
+// Add the flags shared by commands that work with business days
+pub fn business_calendar_flags(signature: Signature) -> Signature {
+    signature
+        .named(
+            "weekend",
+            SyntaxShape::List(Box::new(SyntaxShape::String)),
+            "Weekdays that aren't worked (default [sat sun]).",
+            Some('w'),
+        )
+        .named(
+            "holidays",
+            SyntaxShape::List(Box::new(SyntaxShape::OneOf(vec![
+                SyntaxShape::String,
+                SyntaxShape::DateTime,
+            ]))),
+            "Dates that aren't worked.",
+            Some('H'),
+        )
+        .named(
+            "calendar",
+            SyntaxShape::String,
+            "Holiday calendar whose observed holidays aren't worked, a built-in name like nyse or a calendar file.",
+            Some('C'),
+        )
+}
+
+pub fn get_business_calendar_from_call(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+) -> Result<BusinessCalendar, LabeledError> {
+    let weekend_strings: Option<Vec<Spanned<String>>> = call.get_flag("weekend")?;
+    let holiday_values: Option<Vec<Value>> = call.get_flag("holidays")?;
+    let calendar_name: Option<Spanned<String>> = call.get_flag("calendar")?;
+
+    let holiday_calendar = calendar_name
+        .map(|calendar_name| load_holiday_calendar(engine, calendar_name))
+        .transpose()?;
+
+    let weekend = match weekend_strings {
+        Some(weekend_strings) => weekend_strings
+            .into_iter()
+            .map(get_weekday_from_string)
+            .collect::<Result<Vec<Weekday>, LabeledError>>()?,
+        None => match &holiday_calendar {
+            Some(holiday_calendar) => holiday_calendar.weekend.clone(),
+            None => BusinessCalendar::default().weekend,
+        },
+    };
+    let holidays = match holiday_values {
+        Some(holiday_values) => {
+            let local_tz = local_time_zone(engine);
+            holiday_values
+                .iter()
+                .map(|holiday| {
+                    get_zoned_from_value(holiday, &local_tz).map(|holiday| holiday.date())
+                })
+                .collect::<Result<Vec<civil::Date>, LabeledError>>()?
+        }
+        None => vec![],
+    };
+
+    let calendar = BusinessCalendar::new(weekend, holidays)
+        .map_err(|err| err.with_label("invalid weekend", call.head))?;
+    Ok(match holiday_calendar {
+        Some(holiday_calendar) => calendar.with_holiday_calendar(holiday_calendar),
+        None => calendar,
+    })
+}
+
+// Move a zoned datetime to another date keeping its time of day and time zone
+pub fn with_date(datetime: &Zoned, date: civil::Date) -> Result<Zoned, LabeledError> {
+    date.to_datetime(datetime.time())
+        .to_zoned(datetime.time_zone().clone())
+        .map_err(|err| LabeledError::new(err.to_string()))
+}
+
+// Count business days between two datetimes using the dates they fall on in the
+// first datetime's time zone
+pub fn count_business_days_between(calendar: &BusinessCalendar, start: &Zoned, end: &Zoned) -> i64 {
+    let end = end.with_time_zone(start.time_zone().clone());
+    calendar.count_business_days(start.date(), end.date())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtAddBusinessDays)
+    }
+
+    #[test]
+    fn test_add_business_days_from_weekend() {
+        let calendar = BusinessCalendar::default();
+        // 2024-08-24 is a Saturday
+        assert_eq!(
+            calendar.add_business_days(date(2024, 8, 24), 1).unwrap(),
+            date(2024, 8, 26)
+        );
+        assert_eq!(
+            calendar.add_business_days(date(2024, 8, 24), -1).unwrap(),
+            date(2024, 8, 23)
+        );
+        assert_eq!(
+            calendar.add_business_days(date(2024, 8, 24), 0).unwrap(),
+            date(2024, 8, 24)
+        );
+    }
+
+    #[test]
+    fn test_count_business_days() {
+        let calendar = BusinessCalendar::new(
+            vec![Weekday::Saturday, Weekday::Sunday],
+            vec![date(2024, 8, 27)],
+        )
+        .unwrap();
+        assert_eq!(
+            calendar.count_business_days(date(2024, 8, 23), date(2024, 8, 30)),
+            4
+        );
+        assert_eq!(
+            calendar.count_business_days(date(2024, 8, 30), date(2024, 8, 23)),
+            -4
+        );
+    }
+
+    #[test]
+    fn test_business_days_keep_time_across_dst() {
+        let datetime: Zoned = "2024-03-08T09:00:00-06:00[America/Chicago]"
+            .parse()
+            .unwrap();
+        let calendar = BusinessCalendar::default();
+        let date = calendar.add_business_days(datetime.date(), 1).unwrap();
+        assert_eq!(
+            with_date(&datetime, date).unwrap().to_string(),
+            "2024-03-11T09:00:00-05:00[America/Chicago]"
+        );
+    }
+
+    #[test]
+    fn test_weekend_every_day() {
+        let weekend = (0..7)
+            .map(|day| Weekday::from_monday_zero_offset(day).unwrap())
+            .collect();
+        assert!(BusinessCalendar::new(weekend, vec![]).is_err());
+    }
+}
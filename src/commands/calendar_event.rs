@@ -0,0 +1,665 @@
+use super::tzdb::{local_now, local_time_zone, resolve_time_zone};
+use super::utils::{get_weekday_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{
+    ToSpan, Zoned, civil,
+    tz::{AmbiguousOffset, TimeZone},
+};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Span as NuSpan, Spanned, SyntaxShape, Value, record,
+};
+
+// Same search window as cron, 400 years covers every leap year and weekday combination
+const MAX_SEARCH_DAYS: i64 = 146_097;
+
+const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+pub struct DtCalendarEvent;
+
+impl SimplePluginCommand for DtCalendarEvent {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt calendar-event"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "expression",
+                SyntaxShape::String,
+                "systemd OnCalendar= expression, e.g. 'Mon..Fri *-*-* 09:00:00' or 'quarterly'.",
+            )
+            .named(
+                "count",
+                SyntaxShape::Int,
+                "Number of elapse times to return (default 1).",
+                Some('c'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Normalize a systemd calendar event expression and return its next elapse times after the piped in datetime or now"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Expressions have the form "DayOfWeek Year-Month-Day Hour:Minute:Second TimeZone" where every part is optional. Components support *, lists, a..b ranges and /n repetitions, and the day can be counted from the end of the month with ~, e.g. *-02~01 for the last day of February. minutely, hourly, daily, weekly, monthly, quarterly, semiannually, yearly and annually are also accepted. The time zone can be an IANA name, a TZif file path or a POSIX TZ string.
+
+Elapse times are returned in the expression's time zone or, when it has none, the starting datetime's zone. Times skipped by a DST change never elapse and times repeated by one elapse twice."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "systemd", "timer", "oncalendar", "schedule"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-23T10:00:00+02:00[Europe/Berlin]' | dt calendar-event 'Mon..Fri *-*-* 09:00 Europe/Berlin' --count 2",
+                description: "Normalize a weekday morning timer and return its next two elapse times",
+                result: Some(Value::test_record(record! {
+                    "original" => Value::test_string("Mon..Fri *-*-* 09:00 Europe/Berlin"),
+                    "normalized" => Value::test_string("Mon..Fri *-*-* 09:00:00 Europe/Berlin"),
+                    "next_elapse" => Value::test_list(vec![
+                        Value::test_string("2024-08-26T09:00:00+02:00[Europe/Berlin]"),
+                        Value::test_string("2024-08-27T09:00:00+02:00[Europe/Berlin]"),
+                    ]),
+                })),
+            },
+            Example {
+                example: "'2024-08-23T10:00:00-05:00[America/Chicago]' | dt calendar-event quarterly",
+                description: "Expand a shorthand expression",
+                result: Some(Value::test_record(record! {
+                    "original" => Value::test_string("quarterly"),
+                    "normalized" => Value::test_string("*-01,04,07,10-01 00:00:00"),
+                    "next_elapse" => Value::test_list(vec![
+                        Value::test_string("2024-10-01T00:00:00-05:00[America/Chicago]"),
+                    ]),
+                })),
+            },
+            Example {
+                example: "'2024-08-23T10:00:00-05:00[America/Chicago]' | dt calendar-event '*-*-1/2'",
+                description: "Return the next midnight on an odd day of the month",
+                result: Some(Value::test_record(record! {
+                    "original" => Value::test_string("*-*-1/2"),
+                    "normalized" => Value::test_string("*-*-01/2 00:00:00"),
+                    "next_elapse" => Value::test_list(vec![
+                        Value::test_string("2024-08-25T00:00:00-05:00[America/Chicago]"),
+                    ]),
+                })),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let expression: Spanned<String> = call.req(0)?;
+        let count: Option<Spanned<i64>> = call.get_flag("count")?;
+
+        let count = match count {
+            Some(count) if count.item < 1 => {
+                return Err(LabeledError::new("count must be greater than zero")
+                    .with_label(format!("got {}", count.item), count.span));
+            }
+            Some(count) => count.item as usize,
+            None => 1,
+        };
+        let from = match input {
+            Value::Nothing { .. } => local_now(engine),
+            input => get_zoned_from_value(input, &local_time_zone(engine))?,
+        };
+
+        let event = CalendarEvent::parse(
+            &expression.item,
+            &engine.get_current_dir().unwrap_or_default(),
+            expression.span,
+        )?;
+        let elapses = event.next_elapses(&from, count)?;
+        Ok(Value::record(
+            record! {
+                "original" => Value::string(expression.item, call.head),
+                "normalized" => Value::string(event.to_string(), call.head),
+                "next_elapse" => Value::list(
+                    elapses
+                        .into_iter()
+                        .map(|elapse| Value::string(elapse.to_string(), call.head))
+                        .collect(),
+                    call.head,
+                ),
+            },
+            call.head,
+        ))
+    }
+}
+
+// One start[..stop][/repeat] item of a comma separated component
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Chain {
+    start: i32,
+    stop: Option<i32>,
+    repeat: Option<i32>,
+}
+
+impl Chain {
+    fn contains(&self, value: i32, max: i32) -> bool {
+        let stop = match (self.stop, self.repeat) {
+            (Some(stop), _) => stop,
+            (None, Some(_)) => max,
+            (None, None) => self.start,
+        };
+        value >= self.start
+            && value <= stop
+            && self
+                .repeat
+                .is_none_or(|repeat| (value - self.start) % repeat == 0)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Component {
+    Any,
+    Chains(Vec<Chain>),
+}
+
+impl Component {
+    fn parse(text: &str, min: i32, max: i32) -> Result<Component, String> {
+        if text == "*" {
+            return Ok(Component::Any);
+        }
+        let parse_value = |value: &str| {
+            value
+                .parse::<i32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(|| format!("{value} is not between {min} and {max}"))
+        };
+        let mut chains = vec![];
+        for item in text.split(',') {
+            let (range, repeat) = match item.split_once('/') {
+                Some((range, repeat)) => {
+                    let repeat = repeat
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|repeat| *repeat > 0)
+                        .ok_or_else(|| format!("invalid repetition {repeat}"))?;
+                    (range, Some(repeat))
+                }
+                None => (item, None),
+            };
+            let (start, stop) = match range.split_once("..") {
+                Some((start, stop)) => (parse_value(start)?, Some(parse_value(stop)?)),
+                None if range == "*" && repeat.is_some() => (min, None),
+                None => (parse_value(range)?, None),
+            };
+            if stop.is_some_and(|stop| stop < start) {
+                return Err(format!("range {range} is backwards"));
+            }
+            chains.push(Chain {
+                start,
+                stop,
+                repeat,
+            });
+        }
+        chains.sort();
+        chains.dedup();
+        Ok(Component::Chains(chains))
+    }
+
+    fn contains(&self, value: i32, max: i32) -> bool {
+        match self {
+            Component::Any => true,
+            Component::Chains(chains) => chains.iter().any(|chain| chain.contains(value, max)),
+        }
+    }
+
+    // Days counted back from the end of the month, ~01 is the last day. Ranges and
+    // repetitions still run forwards through the month like systemd does.
+    fn contains_from_end(&self, day: i32, last: i32) -> bool {
+        match self {
+            Component::Any => true,
+            Component::Chains(chains) => chains.iter().any(|chain| {
+                let start = last + 1 - chain.start;
+                let (start, stop) = match chain.stop {
+                    Some(stop) => (start.min(last + 1 - stop), start.max(last + 1 - stop)),
+                    None => (start, if chain.repeat.is_some() { last } else { start }),
+                };
+                Chain {
+                    start,
+                    stop: Some(stop),
+                    repeat: chain.repeat,
+                }
+                .contains(day, last)
+            }),
+        }
+    }
+
+    fn values(&self, min: i32, max: i32) -> Vec<i32> {
+        (min..=max)
+            .filter(|value| self.contains(*value, max))
+            .collect()
+    }
+
+    fn format(&self, width: usize) -> String {
+        match self {
+            Component::Any => "*".to_string(),
+            Component::Chains(chains) => chains
+                .iter()
+                .map(|chain| {
+                    let mut text = format!("{:0width$}", chain.start);
+                    if let Some(stop) = chain.stop {
+                        text.push_str(&format!("..{stop:0width$}"));
+                    }
+                    if let Some(repeat) = chain.repeat {
+                        text.push_str(&format!("/{repeat}"));
+                    }
+                    text
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+}
+
+struct CalendarEvent {
+    // Bit 0 is Monday, None matches every day
+    weekdays: Option<u8>,
+    year: Component,
+    month: Component,
+    day: Component,
+    end_of_month: bool,
+    hour: Component,
+    minute: Component,
+    second: Component,
+    time_zone: Option<(String, TimeZone)>,
+}
+
+impl CalendarEvent {
+    fn parse(expression: &str, cwd: &str, span: NuSpan) -> Result<CalendarEvent, LabeledError> {
+        let error = |msg: String| {
+            LabeledError::new(format!("Invalid calendar event: {msg}"))
+                .with_label("invalid calendar event expression", span)
+        };
+
+        let mut tokens: Vec<&str> = expression.split_whitespace().collect();
+        // a trailing token that isn't a time zone is part of the expression
+        let time_zone = match tokens.as_slice() {
+            [_, .., name] => resolve_time_zone(name, cwd)
+                .ok()
+                .map(|time_zone| (name.to_string(), time_zone)),
+            _ => None,
+        };
+        if time_zone.is_some() {
+            tokens.pop();
+        }
+
+        let shorthand = match tokens.as_slice() {
+            [word] => match word.to_lowercase().as_str() {
+                "minutely" => Some("*-*-* *:*:00"),
+                "hourly" => Some("*-*-* *:00:00"),
+                "daily" => Some("*-*-* 00:00:00"),
+                "weekly" => Some("Mon *-*-* 00:00:00"),
+                "monthly" => Some("*-*-01 00:00:00"),
+                "quarterly" => Some("*-01,04,07,10-01 00:00:00"),
+                "semiannually" => Some("*-01,07-01 00:00:00"),
+                "yearly" | "annually" => Some("*-01-01 00:00:00"),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(shorthand) = shorthand {
+            tokens = shorthand.split_whitespace().collect();
+        }
+        if tokens.is_empty() {
+            return Err(error("expression is empty".to_string()));
+        }
+
+        let mut tokens = tokens.into_iter().peekable();
+        let weekdays = match tokens.peek() {
+            Some(token) if token.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                let token = tokens.next().unwrap_or_default();
+                Some(parse_weekdays(token, span)?)
+            }
+            _ => None,
+        };
+        let (mut date, mut time) = (None, None);
+        for token in tokens {
+            if token.contains(':') && time.is_none() {
+                time = Some(token);
+            } else if date.is_none() && time.is_none() {
+                date = Some(token);
+            } else {
+                return Err(error(format!("unexpected {token:?}")));
+            }
+        }
+
+        let (year, month, day, end_of_month) = match date {
+            Some(date) => {
+                let (year_month, day, end_of_month) = match date.split_once('~') {
+                    Some((year_month, day)) => (year_month, day, true),
+                    None => match date.rsplit_once('-') {
+                        Some((year_month, day)) => (year_month, day, false),
+                        None => return Err(error(format!("{date:?} is not a date"))),
+                    },
+                };
+                let (year, month) = match year_month.split_once('-') {
+                    Some((year, month)) => (year, month),
+                    None => ("*", year_month),
+                };
+                (
+                    Component::parse(year, 1, 9999).map_err(&error)?,
+                    Component::parse(month, 1, 12).map_err(&error)?,
+                    Component::parse(day, 1, 31).map_err(&error)?,
+                    end_of_month,
+                )
+            }
+            None => (Component::Any, Component::Any, Component::Any, false),
+        };
+        let (hour, minute, second) = match time {
+            Some(time) => {
+                let parts: Vec<&str> = time.split(':').collect();
+                let (hour, minute, second) = match parts.as_slice() {
+                    [hour, minute] => (*hour, *minute, "00"),
+                    [hour, minute, second] => (*hour, *minute, *second),
+                    _ => return Err(error(format!("{time:?} is not a time"))),
+                };
+                (
+                    Component::parse(hour, 0, 23).map_err(&error)?,
+                    Component::parse(minute, 0, 59).map_err(&error)?,
+                    Component::parse(second, 0, 59).map_err(&error)?,
+                )
+            }
+            None => {
+                let midnight = Component::Chains(vec![Chain {
+                    start: 0,
+                    stop: None,
+                    repeat: None,
+                }]);
+                (midnight.clone(), midnight.clone(), midnight)
+            }
+        };
+
+        Ok(CalendarEvent {
+            weekdays,
+            year,
+            month,
+            day,
+            end_of_month,
+            hour,
+            minute,
+            second,
+            time_zone,
+        })
+    }
+
+    fn date_matches(&self, date: civil::Date) -> bool {
+        let weekday_bit = 1 << date.weekday().to_monday_zero_offset();
+        let day = i32::from(date.day());
+        let last = i32::from(date.days_in_month());
+        self.weekdays.is_none_or(|bits| bits & weekday_bit != 0)
+            && self.year.contains(i32::from(date.year()), 9999)
+            && self.month.contains(i32::from(date.month()), 12)
+            && if self.end_of_month {
+                self.day.contains_from_end(day, last)
+            } else {
+                self.day.contains(day, last)
+            }
+    }
+
+    // All the instants the event elapses at on a local date, in order
+    fn elapses_on(
+        &self,
+        date: civil::Date,
+        time_zone: &TimeZone,
+    ) -> Result<Vec<Zoned>, LabeledError> {
+        let to_error = |err: jiff::Error| LabeledError::new(err.to_string());
+        let mut elapses = vec![];
+        if !self.date_matches(date) {
+            return Ok(elapses);
+        }
+        let minutes = self.minute.values(0, 59);
+        let seconds = self.second.values(0, 59);
+        for hour in self.hour.values(0, 23) {
+            for minute in &minutes {
+                for second in &seconds {
+                    let time = civil::Time::new(hour as i8, *minute as i8, *second as i8, 0)
+                        .map_err(to_error)?;
+                    let ambiguous = time_zone.to_ambiguous_zoned(date.to_datetime(time));
+                    match ambiguous.offset() {
+                        AmbiguousOffset::Unambiguous { .. } => {
+                            elapses.push(ambiguous.compatible().map_err(to_error)?)
+                        }
+                        AmbiguousOffset::Gap { .. } => {}
+                        AmbiguousOffset::Fold { .. } => {
+                            elapses.push(ambiguous.clone().earlier().map_err(to_error)?);
+                            elapses.push(ambiguous.later().map_err(to_error)?);
+                        }
+                    }
+                }
+            }
+        }
+        elapses.sort();
+        Ok(elapses)
+    }
+
+    fn next_elapses(&self, from: &Zoned, count: usize) -> Result<Vec<Zoned>, LabeledError> {
+        let from = match &self.time_zone {
+            Some((_, time_zone)) => from.with_time_zone(time_zone.clone()),
+            None => from.clone(),
+        };
+        let time_zone = from.time_zone();
+        let mut date = from.date();
+        let mut results = vec![];
+
+        for _ in 0..MAX_SEARCH_DAYS {
+            for elapse in self.elapses_on(date, time_zone)? {
+                if elapse > from {
+                    results.push(elapse);
+                    if results.len() == count {
+                        return Ok(results);
+                    }
+                }
+            }
+            date = match date.checked_add(1.day()) {
+                Ok(date) => date,
+                Err(_) => break,
+            };
+        }
+
+        if results.is_empty() {
+            Err(LabeledError::new(
+                "The calendar event never elapses in the searched range".to_string(),
+            ))
+        } else {
+            Ok(results)
+        }
+    }
+}
+
+impl std::fmt::Display for CalendarEvent {
+    // Format the event the way systemd-analyze calendar shows the normalized form
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(bits) = self.weekdays {
+            let mut runs = vec![];
+            let mut day = 0;
+            while day < 7 {
+                if bits & (1 << day) == 0 {
+                    day += 1;
+                    continue;
+                }
+                let start = day;
+                while day < 7 && bits & (1 << day) != 0 {
+                    day += 1;
+                }
+                match day - start {
+                    1 => runs.push(WEEKDAY_NAMES[start].to_string()),
+                    2 => runs.push(format!(
+                        "{},{}",
+                        WEEKDAY_NAMES[start],
+                        WEEKDAY_NAMES[start + 1]
+                    )),
+                    _ => runs.push(format!(
+                        "{}..{}",
+                        WEEKDAY_NAMES[start],
+                        WEEKDAY_NAMES[day - 1]
+                    )),
+                }
+            }
+            write!(f, "{} ", runs.join(","))?;
+        }
+        write!(
+            f,
+            "{}-{}{}{} {}:{}:{}",
+            self.year.format(4),
+            self.month.format(2),
+            if self.end_of_month { "~" } else { "-" },
+            self.day.format(2),
+            self.hour.format(2),
+            self.minute.format(2),
+            self.second.format(2),
+        )?;
+        if let Some((name, _)) = &self.time_zone {
+            write!(f, " {name}")?;
+        }
+        Ok(())
+    }
+}
+
+// Parse Mon..Fri or Sat,Sun into a bit set with Monday as bit 0
+fn parse_weekdays(token: &str, span: NuSpan) -> Result<u8, LabeledError> {
+    let to_bit = |name: &str| -> Result<i8, LabeledError> {
+        get_weekday_from_string(Spanned {
+            item: name.to_string(),
+            span,
+        })
+        .map(|weekday| weekday.to_monday_zero_offset())
+    };
+    let mut bits = 0u8;
+    for item in token.split(',').filter(|item| !item.is_empty()) {
+        match item.split_once("..") {
+            Some((start, end)) => {
+                let (start, end) = (to_bit(start)?, to_bit(end)?);
+                // Ranges like Sat..Mon wrap around the end of the week
+                let mut day = start;
+                loop {
+                    bits |= 1 << day;
+                    if day == end {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => bits |= 1 << to_bit(item)?,
+        }
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    fn next(expression: &str, from: &str, count: usize) -> Vec<String> {
+        let event = CalendarEvent::parse(expression, "/", NuSpan::unknown()).unwrap();
+        event
+            .next_elapses(&from.parse().unwrap(), count)
+            .unwrap()
+            .into_iter()
+            .map(|elapse| elapse.to_string())
+            .collect()
+    }
+
+    fn normalize(expression: &str) -> String {
+        CalendarEvent::parse(expression, "/", NuSpan::unknown())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtCalendarEvent)
+    }
+
+    #[test]
+    fn test_calendar_event_normalize() {
+        assert_eq!(normalize("minutely"), "*-*-* *:*:00");
+        assert_eq!(normalize("weekly UTC"), "Mon *-*-* 00:00:00 UTC");
+        assert_eq!(normalize("Sat,Sun 12:00"), "Sat,Sun *-*-* 12:00:00");
+        assert_eq!(normalize("fri,mon,tue,wed"), "Mon..Wed,Fri *-*-* 00:00:00");
+        assert_eq!(
+            normalize("2024-2-29 8..17/2:30"),
+            "2024-02-29 08..17/2:30:00"
+        );
+        assert_eq!(normalize("*:*/15"), "*-*-* *:00/15:00");
+        assert_eq!(normalize("daily EST"), "*-*-* 00:00:00 EST");
+        assert_eq!(normalize("daily JST-9"), "*-*-* 00:00:00 JST-9");
+    }
+
+    #[test]
+    fn test_calendar_event_zone_without_slash() {
+        assert_eq!(
+            next("*-*-* 12:00 EST5EDT", "2024-07-01T00:00:00+00:00[UTC]", 1),
+            vec!["2024-07-01T12:00:00-04:00[EST5EDT]"]
+        );
+        assert_eq!(
+            next("daily JST-9", "2024-07-01T00:00:00+00:00[UTC]", 1),
+            vec!["2024-07-02T00:00:00+09:00[+09:00]"]
+        );
+    }
+
+    #[test]
+    fn test_calendar_event_end_of_month() {
+        assert_eq!(
+            next("*-02~03", "2024-01-01T00:00:00+00:00[UTC]", 1),
+            vec!["2024-02-27T00:00:00+00:00[UTC]"]
+        );
+        // The last Monday in May
+        assert_eq!(
+            next("Mon *-05~07/1", "2024-01-01T00:00:00+00:00[UTC]", 1),
+            vec!["2024-05-27T00:00:00+00:00[UTC]"]
+        );
+    }
+
+    #[test]
+    fn test_calendar_event_dst() {
+        // 02:30 doesn't exist on 2024-03-10 in New York
+        assert_eq!(
+            next(
+                "*-*-* 02:30",
+                "2024-03-09T12:00:00-05:00[America/New_York]",
+                2
+            ),
+            vec![
+                "2024-03-11T02:30:00-04:00[America/New_York]",
+                "2024-03-12T02:30:00-04:00[America/New_York]",
+            ]
+        );
+        // 01:30 happens twice on 2024-11-03 in New York
+        assert_eq!(
+            next(
+                "*-*-* 01:30 America/New_York",
+                "2024-11-03T00:00:00+00:00[UTC]",
+                3
+            ),
+            vec![
+                "2024-11-03T01:30:00-04:00[America/New_York]",
+                "2024-11-03T01:30:00-05:00[America/New_York]",
+                "2024-11-04T01:30:00-05:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calendar_event_invalid() {
+        assert!(CalendarEvent::parse("*-13-01", "/", NuSpan::unknown()).is_err());
+        assert!(CalendarEvent::parse("Funday", "/", NuSpan::unknown()).is_err());
+        assert!(CalendarEvent::parse("*-*-* 25:00", "/", NuSpan::unknown()).is_err());
+    }
+}
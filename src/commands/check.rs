@@ -0,0 +1,253 @@
+use super::timezones::format_utc_offset;
+use super::utils::get_time_zone_from_string;
+use crate::DtPlugin;
+use jiff::{
+    civil,
+    fmt::temporal::DateTimeParser,
+    tz::{AmbiguousOffset, Offset, TimeZone},
+};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Span, SyntaxShape, Value, record};
+
+pub struct DtCheck;
+
+impl SimplePluginCommand for DtCheck {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt check"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "zone",
+                SyntaxShape::String,
+                "Time zone the civil datetime is in.",
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Check whether a civil datetime exists once in a time zone, is skipped by a DST gap or is repeated by a fold"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Only the civil date and time of the piped in value are used, any offset or time zone in it is ignored. Candidates are the instants the civil datetime could mean with each offset in effect around it: one for an unambiguous datetime, two for a fold, and for a gap the instants reached with the offsets before and after it, which have a different wall clock time. The compatible column is the instant other dt commands pick, the later one in a gap and the earlier one in a fold."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "dst",
+            "gap",
+            "fold",
+            "ambiguous",
+            "nonexistent",
+            "validate",
+            "time zone",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30' | dt check America/Chicago",
+                description: "Check a civil datetime that exists once",
+                result: Some(Value::test_record(record! {
+                    "datetime" => Value::test_string("2024-08-25T10:52:30"),
+                    "status" => Value::test_string("unambiguous"),
+                    "compatible" => Value::test_string(
+                        "2024-08-25T10:52:30-05:00[America/Chicago]",
+                    ),
+                    "candidates" => Value::test_list(vec![
+                        Value::test_record(record! {
+                            "offset" => Value::test_string("-05:00"),
+                            "instant" => Value::test_string(
+                                "2024-08-25T10:52:30-05:00[America/Chicago]",
+                            ),
+                        }),
+                    ]),
+                })),
+            },
+            Example {
+                example: "'2024-03-10T02:30:00' | dt check America/Chicago",
+                description: "Check a civil datetime skipped when DST starts",
+                result: Some(Value::test_record(record! {
+                    "datetime" => Value::test_string("2024-03-10T02:30:00"),
+                    "status" => Value::test_string("gap"),
+                    "compatible" => Value::test_string(
+                        "2024-03-10T03:30:00-05:00[America/Chicago]",
+                    ),
+                    "candidates" => Value::test_list(vec![
+                        Value::test_record(record! {
+                            "offset" => Value::test_string("-06:00"),
+                            "instant" => Value::test_string(
+                                "2024-03-10T03:30:00-05:00[America/Chicago]",
+                            ),
+                        }),
+                        Value::test_record(record! {
+                            "offset" => Value::test_string("-05:00"),
+                            "instant" => Value::test_string(
+                                "2024-03-10T01:30:00-06:00[America/Chicago]",
+                            ),
+                        }),
+                    ]),
+                })),
+            },
+            Example {
+                example: "'2024-11-03T01:30:00' | dt check America/Chicago",
+                description: "Check a civil datetime repeated when DST ends",
+                result: Some(Value::test_record(record! {
+                    "datetime" => Value::test_string("2024-11-03T01:30:00"),
+                    "status" => Value::test_string("fold"),
+                    "compatible" => Value::test_string(
+                        "2024-11-03T01:30:00-05:00[America/Chicago]",
+                    ),
+                    "candidates" => Value::test_list(vec![
+                        Value::test_record(record! {
+                            "offset" => Value::test_string("-05:00"),
+                            "instant" => Value::test_string(
+                                "2024-11-03T01:30:00-05:00[America/Chicago]",
+                            ),
+                        }),
+                        Value::test_record(record! {
+                            "offset" => Value::test_string("-06:00"),
+                            "instant" => Value::test_string(
+                                "2024-11-03T01:30:00-06:00[America/Chicago]",
+                            ),
+                        }),
+                    ]),
+                })),
+            },
+            Example {
+                example: "open readings.csv | insert dst { |row| $row.taken_at | dt check Europe/Berlin | get status } | where dst != unambiguous",
+                description: "Flag rows whose local times are skipped or repeated",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let time_zone = get_time_zone_from_string(engine, call.req(0)?)?;
+        let datetime = get_civil_datetime_from_value(input)?;
+        check_civil_datetime(datetime, &time_zone, call.head)
+    }
+}
+
+// The civil part of a datetime string or nushell datetime, ignoring its offset
+fn get_civil_datetime_from_value(value: &Value) -> Result<civil::DateTime, LabeledError> {
+    static PARSER: DateTimeParser = DateTimeParser::new();
+    let span = value.span();
+    let text = match value {
+        Value::Date { val, .. } => val.to_rfc3339(),
+        Value::String { val, .. } => val.clone(),
+        _ => {
+            return Err(LabeledError::new("Expected a civil datetime string")
+                .with_label(format!("got {}", value.get_type()), span));
+        }
+    };
+    // jiff won't parse a civil datetime out of one marked as UTC with Z
+    let (datetime, annotation) = text.split_at(text.find('[').unwrap_or(text.len()));
+    let civil_text = match datetime.strip_suffix(['Z', 'z']) {
+        Some(datetime) => format!("{datetime}{annotation}"),
+        None => text.clone(),
+    };
+    PARSER.parse_datetime(&civil_text).map_err(|err| {
+        LabeledError::new(format!("Could not parse civil datetime: {text}"))
+            .with_label(err.to_string(), span)
+    })
+}
+
+fn check_civil_datetime(
+    datetime: civil::DateTime,
+    time_zone: &TimeZone,
+    head: Span,
+) -> Result<Value, LabeledError> {
+    let ambiguous = time_zone.to_ambiguous_zoned(datetime);
+    let (status, offsets) = match ambiguous.offset() {
+        AmbiguousOffset::Unambiguous { offset } => ("unambiguous", vec![offset]),
+        AmbiguousOffset::Gap { before, after } => ("gap", vec![before, after]),
+        AmbiguousOffset::Fold { before, after } => ("fold", vec![before, after]),
+    };
+    let compatible = ambiguous
+        .compatible()
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+    let candidates = offsets
+        .into_iter()
+        .map(|offset: Offset| {
+            let instant = offset
+                .to_timestamp(datetime)
+                .map_err(|err| LabeledError::new(err.to_string()))?
+                .to_zoned(time_zone.clone());
+            Ok(Value::record(
+                record! {
+                    "offset" => Value::string(format_utc_offset(offset), head),
+                    "instant" => Value::string(instant.to_string(), head),
+                },
+                head,
+            ))
+        })
+        .collect::<Result<Vec<Value>, LabeledError>>()?;
+    Ok(Value::record(
+        record! {
+            "datetime" => Value::string(datetime.to_string(), head),
+            "status" => Value::string(status, head),
+            "compatible" => Value::string(compatible.to_string(), head),
+            "candidates" => Value::list(candidates, head),
+        },
+        head,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtCheck)
+    }
+
+    #[test]
+    fn test_get_civil_datetime_from_value() {
+        let civil = |text: &str| get_civil_datetime_from_value(&Value::test_string(text));
+        assert_eq!(
+            civil("2024-03-10T02:30:00-05:00[America/Chicago]").unwrap(),
+            civil::date(2024, 3, 10).at(2, 30, 0, 0)
+        );
+        assert_eq!(
+            civil("2024-03-10").unwrap(),
+            civil::date(2024, 3, 10).at(0, 0, 0, 0)
+        );
+        assert_eq!(
+            civil("2024-03-10T02:30:00Z").unwrap(),
+            civil::date(2024, 3, 10).at(2, 30, 0, 0)
+        );
+        assert!(get_civil_datetime_from_value(&Value::test_int(1)).is_err());
+    }
+
+    #[test]
+    fn test_check_civil_datetime_in_gap_of_whole_day() {
+        // Samoa skipped 2011-12-30 entirely when it moved across the date line
+        let apia = TimeZone::get("Pacific/Apia").unwrap();
+        let checked = check_civil_datetime(
+            civil::date(2011, 12, 30).at(12, 0, 0, 0),
+            &apia,
+            Span::test_data(),
+        )
+        .unwrap();
+        let record = checked.as_record().unwrap();
+        assert_eq!(record.get("status").unwrap().as_str().unwrap(), "gap");
+        assert_eq!(
+            record.get("candidates").unwrap().as_list().unwrap().len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,737 @@
+use super::tzdb::{local_now, local_time_zone};
+use super::utils::{get_time_zone_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{
+    ToSpan, Zoned, civil,
+    civil::Weekday,
+    tz::{AmbiguousOffset, TimeZone},
+};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Span as NuSpan, Spanned, SyntaxShape, Value,
+};
+
+// How many days to search before giving up on an expression that never fires,
+// e.g. `0 0 30 2 *`. 400 years covers every leap year and weekday combination.
+const MAX_SEARCH_DAYS: i64 = 146_097;
+
+pub struct DtCronNext;
+
+impl SimplePluginCommand for DtCronNext {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt cron next"
+    }
+
+    fn signature(&self) -> Signature {
+        cron_signature(self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Return the next fire times of a cron expression after the piped in datetime or now"
+    }
+
+    fn extra_description(&self) -> &str {
+        CRON_EXTRA_DESCRIPTION
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "crontab", "schedule", "upcoming"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-23T10:00:00-05:00[America/Chicago]' | dt cron next '0 9 * * MON-FRI' --count 2",
+                description: "Return the next two weekday 9am fire times",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-08-26T09:00:00-05:00[America/Chicago]"),
+                    Value::test_string("2024-08-27T09:00:00-05:00[America/Chicago]"),
+                ])),
+            },
+            Example {
+                example: "'2024-03-09T12:00:00-05:00[America/New_York]' | dt cron next '30 2 * * *' --count 2",
+                description: "A job scheduled in the skipped DST hour runs as soon as the clocks change",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-03-10T03:00:00-04:00[America/New_York]"),
+                    Value::test_string("2024-03-11T02:30:00-04:00[America/New_York]"),
+                ])),
+            },
+            Example {
+                example: "'2024-08-23T10:00:00+00:00[UTC]' | dt cron next '0 0 12 ? * FRI#2' --timezone Europe/Berlin",
+                description: "Return the next second Friday at noon in Berlin using a 6 field expression with seconds",
+                result: Some(Value::test_list(vec![Value::test_string(
+                    "2024-09-13T12:00:00+02:00[Europe/Berlin]",
+                )])),
+            },
+            Example {
+                example: "dt cron next '@hourly' --count 3",
+                description: "Return the next three hourly fire times from now",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        run_cron(engine, call, input, CronDirection::Next)
+    }
+}
+
+pub struct DtCronPrev;
+
+impl SimplePluginCommand for DtCronPrev {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt cron prev"
+    }
+
+    fn signature(&self) -> Signature {
+        cron_signature(self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Return the previous fire times of a cron expression before the piped in datetime or now"
+    }
+
+    fn extra_description(&self) -> &str {
+        CRON_EXTRA_DESCRIPTION
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "crontab", "schedule", "previous", "last"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![Example {
+            example: "'2024-08-23T10:00:00-05:00[America/Chicago]' | dt cron prev '0 0 L * *' --count 2",
+            description: "Return the last two midnights on the last day of a month",
+            result: Some(Value::test_list(vec![
+                Value::test_string("2024-07-31T00:00:00-05:00[America/Chicago]"),
+                Value::test_string("2024-06-30T00:00:00-05:00[America/Chicago]"),
+            ])),
+        }]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        run_cron(engine, call, input, CronDirection::Prev)
+    }
+}
+
+const CRON_EXTRA_DESCRIPTION: &str = r#"Expressions have 5 fields (minute hour day-of-month month day-of-week) or 6 fields with seconds first. Fields support *, ?, lists, ranges, steps and JAN-DEC / SUN-SAT names. Day-of-month supports L, L-n, nW and LW, day-of-week supports nL (last) and n#k (k-th). @yearly, @monthly, @weekly, @daily and @hourly are also accepted. When both day fields are restricted a day matching either one fires, when one starts with *, like */2, a day has to match both.
+
+DST is handled like Vixie cron: jobs at a fixed time that falls in a skipped hour run right after the clocks change and run only once in a repeated hour, while jobs with * in the minute or hour field skip missing times and run again in a repeated hour."#;
+
+fn cron_signature(name: &str) -> Signature {
+    Signature::build(name)
+        .required(
+            "expression",
+            SyntaxShape::String,
+            "Cron expression with 5 or 6 fields.",
+        )
+        .named(
+            "count",
+            SyntaxShape::Int,
+            "Number of fire times to return (default 1).",
+            Some('c'),
+        )
+        .named(
+            "timezone",
+            SyntaxShape::String,
+            "IANA time zone to evaluate the expression in (defaults to the datetime's zone).",
+            Some('z'),
+        )
+        .category(Category::Date)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CronDirection {
+    Next,
+    Prev,
+}
+
+fn run_cron(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+    input: &Value,
+    direction: CronDirection,
+) -> Result<Value, LabeledError> {
+    let expression: Spanned<String> = call.req(0)?;
+    let count: Option<Spanned<i64>> = call.get_flag("count")?;
+    let time_zone: Option<Spanned<String>> = call.get_flag("timezone")?;
+
+    let count = match count {
+        Some(count) if count.item < 1 => {
+            return Err(LabeledError::new("count must be greater than zero")
+                .with_label(format!("got {}", count.item), count.span));
+        }
+        Some(count) => count.item as usize,
+        None => 1,
+    };
+    let mut from = match input {
+        Value::Nothing { .. } => local_now(engine),
+        input => get_zoned_from_value(input, &local_time_zone(engine))?,
+    };
+    if let Some(time_zone) = time_zone {
+        from = from.with_time_zone(get_time_zone_from_string(engine, time_zone)?);
+    }
+
+    let schedule = CronSchedule::parse(&expression.item, expression.span)?;
+    let fire_times = schedule.fire_times(&from, direction, count)?;
+    Ok(Value::list(
+        fire_times
+            .into_iter()
+            .map(|fire_time| Value::string(fire_time.to_string(), call.head))
+            .collect(),
+        call.head,
+    ))
+}
+
+struct CronSchedule {
+    seconds: Vec<bool>,
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    months: Vec<bool>,
+    days_of_month: DayOfMonthField,
+    days_of_week: DayOfWeekField,
+    // Vixie cron treats jobs with * in the minute or hour field differently across DST
+    wildcard_time: bool,
+}
+
+struct DayOfMonthField {
+    restricted: bool,
+    days: Vec<bool>,
+    // L is 0, L-3 is 3
+    days_before_last: Vec<i8>,
+    nearest_weekdays: Vec<i8>,
+    last_weekday: bool,
+}
+
+struct DayOfWeekField {
+    restricted: bool,
+    // Sunday is 0
+    days: Vec<bool>,
+    last: Vec<Weekday>,
+    nth: Vec<(Weekday, i8)>,
+}
+
+impl CronSchedule {
+    fn parse(expression: &str, span: NuSpan) -> Result<CronSchedule, LabeledError> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expression => expression,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let (second, rest) = match fields.len() {
+            5 => ("0", &fields[..]),
+            6 => (fields[0], &fields[1..]),
+            _ => {
+                return Err(LabeledError::new(format!(
+                    "Expected 5 or 6 cron fields but found {}",
+                    fields.len()
+                ))
+                .with_label("invalid cron expression", span));
+            }
+        };
+        let field_error = |name: &str, field: &str, msg: String| {
+            LabeledError::new(format!("Invalid cron {name} field {field:?}: {msg}"))
+                .with_label("invalid cron expression", span)
+        };
+
+        let seconds =
+            parse_cron_set(second, 0, 59, &[]).map_err(|msg| field_error("second", second, msg))?;
+        let minutes = parse_cron_set(rest[0], 0, 59, &[])
+            .map_err(|msg| field_error("minute", rest[0], msg))?;
+        let hours =
+            parse_cron_set(rest[1], 0, 23, &[]).map_err(|msg| field_error("hour", rest[1], msg))?;
+        let days_of_month =
+            parse_day_of_month(rest[2]).map_err(|msg| field_error("day-of-month", rest[2], msg))?;
+        let months = parse_cron_set(rest[3], 1, 12, &MONTH_NAMES)
+            .map_err(|msg| field_error("month", rest[3], msg))?;
+        let days_of_week =
+            parse_day_of_week(rest[4]).map_err(|msg| field_error("day-of-week", rest[4], msg))?;
+
+        Ok(CronSchedule {
+            seconds,
+            minutes,
+            hours,
+            months,
+            days_of_month,
+            days_of_week,
+            wildcard_time: rest[0].starts_with('*') || rest[1].starts_with('*'),
+        })
+    }
+
+    fn date_matches(&self, date: civil::Date) -> bool {
+        if !self.months[date.month() as usize] {
+            return false;
+        }
+        let dom = &self.days_of_month;
+        let dow = &self.days_of_week;
+        // Like Vixie cron, when both day fields are restricted either one can match.
+        // A field starting with *, like */2, isn't restricted, so both have to match
+        if dom.restricted && dow.restricted {
+            dom.matches(date) || dow.matches(date)
+        } else {
+            dom.matches(date) && dow.matches(date)
+        }
+    }
+
+    // All the instants the schedule fires at on a local date, in order
+    fn fire_times_on(
+        &self,
+        date: civil::Date,
+        time_zone: &TimeZone,
+    ) -> Result<Vec<Zoned>, LabeledError> {
+        let to_error = |err: jiff::Error| LabeledError::new(err.to_string());
+        let mut fire_times = vec![];
+        if !self.date_matches(date) {
+            return Ok(fire_times);
+        }
+        for hour in set_values(&self.hours) {
+            for minute in set_values(&self.minutes) {
+                for second in set_values(&self.seconds) {
+                    let datetime = date
+                        .to_datetime(civil::Time::new(hour, minute, second, 0).map_err(to_error)?);
+                    let ambiguous = time_zone.to_ambiguous_zoned(datetime);
+                    match ambiguous.offset() {
+                        AmbiguousOffset::Unambiguous { .. } => {
+                            fire_times.push(ambiguous.compatible().map_err(to_error)?)
+                        }
+                        AmbiguousOffset::Gap { .. } => {
+                            if !self.wildcard_time {
+                                // Run at the moment the clocks jump forward
+                                let earlier = ambiguous.earlier().map_err(to_error)?;
+                                if let Some(transition) =
+                                    time_zone.following(earlier.timestamp()).next()
+                                {
+                                    fire_times
+                                        .push(transition.timestamp().to_zoned(time_zone.clone()));
+                                }
+                            }
+                        }
+                        AmbiguousOffset::Fold { .. } => {
+                            fire_times.push(ambiguous.clone().earlier().map_err(to_error)?);
+                            if self.wildcard_time {
+                                fire_times.push(ambiguous.later().map_err(to_error)?);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        fire_times.sort();
+        fire_times.dedup();
+        Ok(fire_times)
+    }
+
+    fn fire_times(
+        &self,
+        from: &Zoned,
+        direction: CronDirection,
+        count: usize,
+    ) -> Result<Vec<Zoned>, LabeledError> {
+        let time_zone = from.time_zone();
+        let step = if direction == CronDirection::Next {
+            1.day()
+        } else {
+            (-1).day()
+        };
+        // Start a day early so fire times moved across midnight by DST aren't missed
+        let mut date = from
+            .date()
+            .checked_sub(step)
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        let mut results: Vec<Zoned> = vec![];
+
+        for _ in 0..MAX_SEARCH_DAYS {
+            let mut day_times = self.fire_times_on(date, time_zone)?;
+            if direction == CronDirection::Prev {
+                day_times.reverse();
+            }
+            for fire_time in day_times {
+                let in_range = match direction {
+                    CronDirection::Next => fire_time > *from,
+                    CronDirection::Prev => fire_time < *from,
+                };
+                let in_order = results.last().is_none_or(|last| match direction {
+                    CronDirection::Next => fire_time > *last,
+                    CronDirection::Prev => fire_time < *last,
+                });
+                if in_range && in_order {
+                    results.push(fire_time);
+                    if results.len() == count {
+                        return Ok(results);
+                    }
+                }
+            }
+            date = match date.checked_add(step) {
+                Ok(date) => date,
+                Err(_) => break,
+            };
+        }
+
+        if results.is_empty() {
+            Err(LabeledError::new(
+                "The cron expression never fires in the searched range".to_string(),
+            ))
+        } else {
+            Ok(results)
+        }
+    }
+}
+
+impl DayOfMonthField {
+    fn matches(&self, date: civil::Date) -> bool {
+        let day = date.day();
+        let last = date.days_in_month();
+        if self.days[day as usize] {
+            return true;
+        }
+        if self
+            .days_before_last
+            .iter()
+            .any(|before| last - before == day)
+        {
+            return true;
+        }
+        let is_weekday = |day: i8| {
+            civil::Date::new(date.year(), date.month(), day)
+                .map(|date| !matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday))
+                .unwrap_or(false)
+        };
+        if self.last_weekday && is_weekday(day) && (day + 1..=last).all(|after| !is_weekday(after))
+        {
+            return true;
+        }
+        self.nearest_weekdays
+            .iter()
+            .any(|target| nearest_weekday(date, *target) == Some(day))
+    }
+}
+
+impl DayOfWeekField {
+    fn matches(&self, date: civil::Date) -> bool {
+        let weekday = date.weekday();
+        if self.days[weekday.to_sunday_zero_offset() as usize] {
+            return true;
+        }
+        let days_left = date.days_in_month() - date.day();
+        if self.last.contains(&weekday) && days_left < 7 {
+            return true;
+        }
+        self.nth
+            .iter()
+            .any(|(nth_weekday, nth)| *nth_weekday == weekday && (date.day() - 1) / 7 + 1 == *nth)
+    }
+}
+
+// The weekday nearest to a day of the month without leaving the month, as with nW
+fn nearest_weekday(date: civil::Date, target: i8) -> Option<i8> {
+    let last = date.days_in_month();
+    if target > last {
+        return None;
+    }
+    let target_date = civil::Date::new(date.year(), date.month(), target).ok()?;
+    Some(match target_date.weekday() {
+        Weekday::Saturday if target == 1 => 3,
+        Weekday::Saturday => target - 1,
+        Weekday::Sunday if target == last => target - 2,
+        Weekday::Sunday => target + 1,
+        _ => target,
+    })
+}
+
+const MONTH_NAMES: [(&str, i8); 12] = [
+    ("JAN", 1),
+    ("FEB", 2),
+    ("MAR", 3),
+    ("APR", 4),
+    ("MAY", 5),
+    ("JUN", 6),
+    ("JUL", 7),
+    ("AUG", 8),
+    ("SEP", 9),
+    ("OCT", 10),
+    ("NOV", 11),
+    ("DEC", 12),
+];
+
+const WEEKDAY_NAMES: [(&str, i8); 7] = [
+    ("SUN", 0),
+    ("MON", 1),
+    ("TUE", 2),
+    ("WED", 3),
+    ("THU", 4),
+    ("FRI", 5),
+    ("SAT", 6),
+];
+
+fn parse_cron_value(value: &str, min: i8, max: i8, names: &[(&str, i8)]) -> Result<i8, String> {
+    let upper = value.to_uppercase();
+    if let Some((_, number)) = names.iter().find(|(name, _)| *name == upper) {
+        return Ok(*number);
+    }
+    value
+        .parse::<i8>()
+        .ok()
+        .filter(|number| (min..=max).contains(number))
+        .ok_or_else(|| format!("{value} is not between {min} and {max}"))
+}
+
+// Parse a list of *, single values, ranges and steps into a set indexed by value
+fn parse_cron_set(
+    field: &str,
+    min: i8,
+    max: i8,
+    names: &[(&str, i8)],
+) -> Result<Vec<bool>, String> {
+    let mut set = vec![false; max as usize + 1];
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<i8>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step {step}"))?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+        let (start, end) = if range == "*" || range == "?" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_cron_value(start, min, max, names)?,
+                parse_cron_value(end, min, max, names)?,
+            )
+        } else {
+            let start = parse_cron_value(range, min, max, names)?;
+            // a/n means every n starting at a
+            (start, if item.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(format!("range {range} is backwards"));
+        }
+        let mut value = start;
+        while value <= end {
+            set[value as usize] = true;
+            value = match value.checked_add(step) {
+                Some(value) => value,
+                None => break,
+            };
+        }
+    }
+    Ok(set)
+}
+
+fn parse_day_of_month(field: &str) -> Result<DayOfMonthField, String> {
+    let mut parsed = DayOfMonthField {
+        restricted: !(field.starts_with('*') || field == "?"),
+        days: vec![false; 32],
+        days_before_last: vec![],
+        nearest_weekdays: vec![],
+        last_weekday: false,
+    };
+    let mut plain_items = vec![];
+    for item in field.split(',') {
+        let upper = item.to_uppercase();
+        if upper == "L" {
+            parsed.days_before_last.push(0);
+        } else if upper == "LW" || upper == "WL" {
+            parsed.last_weekday = true;
+        } else if let Some(before) = upper.strip_prefix("L-") {
+            parsed
+                .days_before_last
+                .push(parse_cron_value(before, 0, 30, &[])?);
+        } else if let Some(day) = upper.strip_suffix('W') {
+            parsed
+                .nearest_weekdays
+                .push(parse_cron_value(day, 1, 31, &[])?);
+        } else {
+            plain_items.push(item);
+        }
+    }
+    if !plain_items.is_empty() {
+        parsed.days = parse_cron_set(&plain_items.join(","), 1, 31, &[])?;
+    }
+    Ok(parsed)
+}
+
+fn parse_day_of_week(field: &str) -> Result<DayOfWeekField, String> {
+    let mut parsed = DayOfWeekField {
+        restricted: !(field.starts_with('*') || field == "?"),
+        days: vec![false; 7],
+        last: vec![],
+        nth: vec![],
+    };
+    let to_weekday = |value: &str| -> Result<Weekday, String> {
+        // 7 is also Sunday
+        let number = parse_cron_value(value, 0, 7, &WEEKDAY_NAMES)?;
+        Weekday::from_sunday_zero_offset(number % 7).map_err(|err| err.to_string())
+    };
+    let mut plain_items = vec![];
+    for item in field.split(',') {
+        let upper = item.to_uppercase();
+        if let Some((weekday, nth)) = upper.split_once('#') {
+            let nth = parse_cron_value(nth, 1, 5, &[])?;
+            parsed.nth.push((to_weekday(weekday)?, nth));
+        } else if let Some(weekday) = upper.strip_suffix('L').filter(|day| !day.is_empty()) {
+            parsed.last.push(to_weekday(weekday)?);
+        } else {
+            plain_items.push(upper);
+        }
+    }
+    if !plain_items.is_empty() {
+        let set = parse_cron_set(&plain_items.join(","), 0, 7, &WEEKDAY_NAMES)?;
+        for (number, enabled) in set.into_iter().enumerate() {
+            if enabled {
+                parsed.days[number % 7] = true;
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+fn set_values(set: &[bool]) -> impl Iterator<Item = i8> + '_ {
+    set.iter()
+        .enumerate()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(value, _)| value as i8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    fn next(expression: &str, from: &str, count: usize) -> Vec<String> {
+        let schedule = CronSchedule::parse(expression, NuSpan::unknown()).unwrap();
+        schedule
+            .fire_times(&from.parse().unwrap(), CronDirection::Next, count)
+            .unwrap()
+            .into_iter()
+            .map(|fire_time| fire_time.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtCronNext)?;
+        plugin_test.test_command_examples(&DtCronPrev)
+    }
+
+    #[test]
+    fn test_cron_wildcard_hour_repeats_in_fold() {
+        assert_eq!(
+            next(
+                "30 * * * *",
+                "2024-11-03T00:45:00-04:00[America/New_York]",
+                3
+            ),
+            vec![
+                "2024-11-03T01:30:00-04:00[America/New_York]",
+                "2024-11-03T01:30:00-05:00[America/New_York]",
+                "2024-11-03T02:30:00-05:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cron_fixed_time_runs_once_in_fold() {
+        assert_eq!(
+            next(
+                "30 1 * * *",
+                "2024-11-03T00:45:00-04:00[America/New_York]",
+                2
+            ),
+            vec![
+                "2024-11-03T01:30:00-04:00[America/New_York]",
+                "2024-11-04T01:30:00-05:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cron_day_of_month_or_day_of_week() {
+        // Runs on the 13th and on every Friday
+        assert_eq!(
+            next("0 0 13 * FRI", "2024-09-01T00:00:00+00:00[UTC]", 3),
+            vec![
+                "2024-09-06T00:00:00+00:00[UTC]",
+                "2024-09-13T00:00:00+00:00[UTC]",
+                "2024-09-20T00:00:00+00:00[UTC]",
+            ]
+        );
+        // Runs on Mondays that are odd days of the month
+        assert_eq!(
+            next("0 0 */2 * MON", "2024-09-01T00:00:00+00:00[UTC]", 3),
+            vec![
+                "2024-09-09T00:00:00+00:00[UTC]",
+                "2024-09-23T00:00:00+00:00[UTC]",
+                "2024-10-07T00:00:00+00:00[UTC]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cron_nearest_weekday_and_last_weekday() {
+        // 2024-06-15 is a Saturday and 2024-06-30 is a Sunday
+        assert_eq!(
+            next("0 12 15W * *", "2024-06-01T00:00:00+00:00[UTC]", 1),
+            vec!["2024-06-14T12:00:00+00:00[UTC]"]
+        );
+        assert_eq!(
+            next("0 12 LW * *", "2024-06-01T00:00:00+00:00[UTC]", 1),
+            vec!["2024-06-28T12:00:00+00:00[UTC]"]
+        );
+    }
+
+    #[test]
+    fn test_cron_steps_and_names() {
+        assert_eq!(
+            next(
+                "*/20 9-10 * JAN,FEB 1-5",
+                "2024-01-31T10:30:00+00:00[UTC]",
+                3
+            ),
+            vec![
+                "2024-01-31T10:40:00+00:00[UTC]",
+                "2024-02-01T09:00:00+00:00[UTC]",
+                "2024-02-01T09:20:00+00:00[UTC]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cron_never_fires() {
+        let schedule = CronSchedule::parse("0 0 30 2 *", NuSpan::unknown()).unwrap();
+        let from: Zoned = "2024-01-01T00:00:00+00:00[UTC]".parse().unwrap();
+        assert!(schedule.fire_times(&from, CronDirection::Next, 1).is_err());
+    }
+
+    #[test]
+    fn test_cron_invalid() {
+        assert!(CronSchedule::parse("0 25 * * *", NuSpan::unknown()).is_err());
+        assert!(CronSchedule::parse("0 * * *", NuSpan::unknown()).is_err());
+    }
+}
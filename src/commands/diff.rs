@@ -1,6 +1,10 @@
+use super::business_days::{
+    business_calendar_flags, count_business_days_between, get_business_calendar_from_call,
+};
+use super::tzdb::local_time_zone;
 use super::utils::{
     create_nushelly_duration_string, get_single_duration_unit_from_span, get_unit_abbreviations,
-    get_unit_from_unit_string, parse_datetime_string_add_nanos_optionally,
+    get_unit_from_unit_string, get_zoned_from_value, parse_datetime_string_add_nanos_optionally,
 };
 use crate::DtPlugin;
 use jiff::{RoundMode, Unit, ZonedDifference, tz::TimeZone};
@@ -17,7 +21,7 @@ impl SimplePluginCommand for DtDiff {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(self.name())
+        business_calendar_flags(Signature::build(self.name()))
             .required(
                 "date",
                 SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
@@ -42,6 +46,11 @@ impl SimplePluginCommand for DtDiff {
                 Some('a'),
             )
             .switch("list", "List the unit name abbreviations", Some('l'))
+            .switch(
+                "business",
+                "Return the number of business days from the first date up to the second",
+                Some('B'),
+            )
             .category(Category::Date)
     }
 
@@ -68,6 +77,11 @@ impl SimplePluginCommand for DtDiff {
                 description: "Return the difference as years, months, and days in the iso8601 duration format",
                 result: Some(Value::test_string("P5y2m28d\n5yrs 2mths 4wks")),
             },
+            Example {
+                example: "'2024-08-23T09:00:00-05:00[America/Chicago]' | dt diff '2024-09-03T09:00:00-05:00[America/Chicago]' --business --holidays [2024-09-02]",
+                description: "Return the number of business days between two datetimes, skipping weekends and a holiday",
+                result: Some(Value::test_int(6)),
+            },
             Example {
                 example: "'2019-05-10T09:59:12-07:00' | dt diff (dt now)",
                 description: "Return the difference in the iso8601 duration format using the current datetime from dt as input",
@@ -124,11 +138,13 @@ impl SimplePluginCommand for DtDiff {
     fn run(
         &self,
         _plugin: &DtPlugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
         let list = call.has_flag("list")?;
+        let business = call.has_flag("business")?;
         let smallest_unit_opt: Option<String> = call.get_flag("smallest")?;
         let biggest_unit_opt: Option<String> = call.get_flag("biggest")?;
         let as_unit_opt: Option<String> = call.get_flag("as")?;
@@ -143,6 +159,19 @@ impl SimplePluginCommand for DtDiff {
 
         if list {
             Ok(Value::list(get_unit_abbreviations(), call.head))
+        } else if business {
+            if smallest_unit_opt.is_some() || biggest_unit_opt.is_some() || as_unit_opt.is_some() {
+                return Err(LabeledError::new(
+                    "Please provide either business or smallest, biggest or as unit. Business is mutually exclusive from the units.".to_string(),
+                ));
+            }
+            let calendar = get_business_calendar_from_call(engine, call)?;
+            let start = get_zoned_from_value(input, &local_tz)?;
+            let end = get_zoned_from_value(&parameter_datetime_provided, &local_tz)?;
+            Ok(Value::int(
+                count_business_days_between(&calendar, &start, &end),
+                span,
+            ))
         } else {
             calculate_date_diff(
                 parameter_datetime_provided,
@@ -151,6 +180,7 @@ impl SimplePluginCommand for DtDiff {
                 smallest_unit_opt,
                 as_unit_opt,
                 span,
+                &local_tz,
             )
         }
     }
@@ -163,6 +193,7 @@ fn calculate_date_diff(
     smallest_unit_opt: Option<String>,
     as_unit_opt: Option<String>,
     call_span: Span,
+    local_tz: &TimeZone,
 ) -> Result<Value, LabeledError> {
     let param_span = parameter_datetime_provided.span();
     let piped_span = piped_in_input.span();
@@ -181,17 +212,28 @@ fn calculate_date_diff(
     let mut zoned_input_datetime = match piped_in_input {
         Value::Date { val, .. } => {
             // eprintln!("Date rfc3339: {:?}", &val.to_rfc3339());
-            parse_datetime_string_add_nanos_optionally(&val.to_rfc3339(), None, piped_span, None)?
+            parse_datetime_string_add_nanos_optionally(
+                &val.to_rfc3339(),
+                None,
+                piped_span,
+                None,
+                local_tz,
+            )?
         }
         Value::String { val, .. } => {
-            parse_datetime_string_add_nanos_optionally(val, None, piped_span, None)?
+            parse_datetime_string_add_nanos_optionally(val, None, piped_span, None, local_tz)?
         }
         _ => return Err(LabeledError::new("Expected a date or datetime".to_string())),
     };
 
     // convert parameter_datetime into a jiff::Zoned
-    let mut zoned_parameter_datetime =
-        parse_datetime_string_add_nanos_optionally(&parameter_datetime, None, param_span, None)?;
+    let mut zoned_parameter_datetime = parse_datetime_string_add_nanos_optionally(
+        &parameter_datetime,
+        None,
+        param_span,
+        None,
+        local_tz,
+    )?;
 
     // Check to see if biggest_unit_opt and smallest_unit_opt are both provided or as_unit_opt is provided
     if (biggest_unit_opt.is_some() || smallest_unit_opt.is_some()) && as_unit_opt.is_some() {
@@ -294,6 +336,7 @@ mod tests {
             None,
             None,
             Span::unknown(),
+            &TimeZone::system(),
         )?;
 
         assert_eq!(
@@ -316,6 +359,7 @@ mod tests {
             None,
             Some("hr".to_string()),
             Span::unknown(),
+            &TimeZone::system(),
         )?;
 
         assert_eq!(result.into_string()?, "PT46294h\n46294hrs");
@@ -335,6 +379,7 @@ mod tests {
             Some("day".to_string()),
             None,
             Span::unknown(),
+            &TimeZone::system(),
         )?;
 
         assert_eq!(result.into_string()?, "P5y3m10d\n5yrs 3mths 1wks 3days");
@@ -354,6 +399,7 @@ mod tests {
             None,
             None,
             Span::unknown(),
+            &TimeZone::system(),
         );
 
         assert!(result.is_err());
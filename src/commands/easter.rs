@@ -0,0 +1,199 @@
+use crate::DtPlugin;
+use jiff::{ToSpan, civil};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Record, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+pub struct DtEaster;
+
+impl SimplePluginCommand for DtEaster {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt easter"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required("year", SyntaxShape::Int, "Year to find Easter Sunday in.")
+            .named(
+                "calendar",
+                SyntaxShape::String,
+                "Church calendar: western (default) or orthodox.",
+                Some('c'),
+            )
+            .switch(
+                "feasts",
+                "Return a record of the moveable feasts that depend on Easter",
+                Some('f'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return the date of Easter Sunday in a year"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Orthodox Easter is calculated on the Julian calendar and returned as a Gregorian date. With --feasts the western calendar returns Ash Wednesday and the orthodox calendar returns Clean Monday as the start of Lent."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "holiday", "computus", "pascha", "lent", "pentecost"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt easter 2025",
+                description: "Return the date of Western Easter Sunday",
+                result: Some(Value::test_string("2025-04-20")),
+            },
+            Example {
+                example: "dt easter 2024 --calendar orthodox",
+                description: "Return the date of Orthodox Easter Sunday",
+                result: Some(Value::test_string("2024-05-05")),
+            },
+            Example {
+                example: "dt easter 2025 --feasts",
+                description: "Return the moveable feasts that depend on Easter",
+                result: Some(Value::test_record(record! {
+                    "ash_wednesday" => Value::test_string("2025-03-05"),
+                    "good_friday" => Value::test_string("2025-04-18"),
+                    "easter" => Value::test_string("2025-04-20"),
+                    "ascension" => Value::test_string("2025-05-29"),
+                    "pentecost" => Value::test_string("2025-06-08"),
+                })),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let year: Spanned<i64> = call.req(0)?;
+        let calendar: Option<Spanned<String>> = call.get_flag("calendar")?;
+        let feasts = call.has_flag("feasts")?;
+
+        let year_number = i16::try_from(year.item)
+            .ok()
+            .filter(|year| (1..=9998).contains(year))
+            .ok_or_else(|| {
+                LabeledError::new("year must be between 1 and 9998")
+                    .with_label(format!("got {}", year.item), year.span)
+            })?;
+        let orthodox = match calendar {
+            None => false,
+            Some(calendar) => match calendar.item.to_lowercase().as_str() {
+                "western" | "gregorian" => false,
+                "orthodox" | "eastern" | "julian" => true,
+                _ => {
+                    return Err(
+                        LabeledError::new(format!("Invalid calendar: {}", calendar.item))
+                            .with_label("expected western or orthodox", calendar.span),
+                    );
+                }
+            },
+        };
+
+        let easter = if orthodox {
+            orthodox_easter(year_number)?
+        } else {
+            western_easter(year_number)?
+        };
+        if !feasts {
+            return Ok(Value::string(easter.to_string(), call.head));
+        }
+
+        let lent_start = if orthodox {
+            ("clean_monday", -48)
+        } else {
+            ("ash_wednesday", -46)
+        };
+        let mut record = Record::new();
+        for (name, offset) in [
+            lent_start,
+            ("good_friday", -2),
+            ("easter", 0),
+            ("ascension", 39),
+            ("pentecost", 49),
+        ] {
+            let feast = easter
+                .checked_add(i64::from(offset).days())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            record.push(name, Value::string(feast.to_string(), call.head));
+        }
+        Ok(Value::record(record, call.head))
+    }
+}
+
+// Western (Gregorian) Easter Sunday using the anonymous Gregorian algorithm
+pub fn western_easter(year: i16) -> Result<civil::Date, LabeledError> {
+    let y = i32::from(year);
+    let a = y.rem_euclid(19);
+    let b = y.div_euclid(100);
+    let c = y.rem_euclid(100);
+    let d = b.div_euclid(4);
+    let e = b.rem_euclid(4);
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let i = c.div_euclid(4);
+    let k = c.rem_euclid(4);
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l).div_euclid(451);
+    let month = (h + l - 7 * m + 114).div_euclid(31);
+    let day = (h + l - 7 * m + 114).rem_euclid(31) + 1;
+    civil::Date::new(year, month as i8, day as i8).map_err(|err| LabeledError::new(err.to_string()))
+}
+
+// Orthodox Easter Sunday using Meeus' Julian algorithm, shifted from the Julian
+// calendar onto the Gregorian one. The gap between the calendars grows by a day in
+// every century year that isn't divisible by 400.
+pub fn orthodox_easter(year: i16) -> Result<civil::Date, LabeledError> {
+    let y = i32::from(year);
+    let a = y.rem_euclid(4);
+    let b = y.rem_euclid(7);
+    let c = y.rem_euclid(19);
+    let d = (19 * c + 15).rem_euclid(30);
+    let e = (2 * a + 4 * b - d + 34).rem_euclid(7);
+    let month = (d + e + 114).div_euclid(31);
+    let day = (d + e + 114).rem_euclid(31) + 1;
+    let julian_gap = y.div_euclid(100) - y.div_euclid(400) - 2;
+    civil::Date::new(year, month as i8, day as i8)
+        .and_then(|julian| julian.checked_add(i64::from(julian_gap).days()))
+        .map_err(|err| LabeledError::new(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtEaster)
+    }
+
+    #[test]
+    fn test_western_easter() {
+        assert_eq!(western_easter(2024).unwrap(), date(2024, 3, 31));
+        assert_eq!(western_easter(2025).unwrap(), date(2025, 4, 20));
+        assert_eq!(western_easter(2038).unwrap(), date(2038, 4, 25));
+        assert_eq!(western_easter(1818).unwrap(), date(1818, 3, 22));
+    }
+
+    #[test]
+    fn test_orthodox_easter() {
+        assert_eq!(orthodox_easter(2023).unwrap(), date(2023, 4, 16));
+        assert_eq!(orthodox_easter(2024).unwrap(), date(2024, 5, 5));
+        assert_eq!(orthodox_easter(2025).unwrap(), date(2025, 4, 20));
+        assert_eq!(orthodox_easter(2100).unwrap(), date(2100, 5, 2));
+    }
+}
@@ -0,0 +1,505 @@
+use super::tzdb::local_time_zone;
+use super::utils::{get_weekday_from_string, get_zoned_from_value, is_quarter_unit_string};
+use crate::DtPlugin;
+use jiff::{ToSpan, Zoned, civil, civil::Weekday};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+pub struct DtFiscalPeriod;
+
+impl SimplePluginCommand for DtFiscalPeriod {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt fiscal-period"
+    }
+
+    fn signature(&self) -> Signature {
+        fiscal_calendar_flags(Signature::build(self.name()).required(
+            "unit",
+            SyntaxShape::String,
+            "Fiscal period unit: year, quarter, period or week.",
+        ))
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return the fiscal period containing the provided date or datetime with its first instant and last nanosecond"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Without --fiscal-pattern fiscal periods are calendar months counted from --fiscal-start. With a 4-4-5, 4-5-4 or 5-4-4 pattern periods are whole weeks and the year ends on the last --fiscal-end-day of the month before --fiscal-start, or the one nearest the end of that month with --fiscal-nearest. Years with a 53rd week add it to the last period."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "date", "fiscal", "finance", "retail", "4-4-5", "quarter", "period",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt fiscal-period quarter --fiscal-start 10",
+                description: "Return the fiscal quarter of a fiscal year starting in October",
+                result: Some(Value::test_record(record! {
+                    "fiscal_year" => Value::test_int(2024),
+                    "fiscal_quarter" => Value::test_int(4),
+                    "fiscal_period" => Value::test_int(11),
+                    "fiscal_week" => Value::test_int(48),
+                    "start" => Value::test_string("2024-07-01T00:00:00-05:00[America/Chicago]"),
+                    "end" => Value::test_string("2024-09-30T23:59:59.999999999-05:00[America/Chicago]"),
+                })),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt fiscal-period quarter --fiscal-start 2 --fiscal-pattern 4-4-5 --fiscal-nearest --fiscal-label start",
+                description: "Return the fiscal quarter of a 4-4-5 retail calendar ending on the Saturday nearest the end of January",
+                result: Some(Value::test_record(record! {
+                    "fiscal_year" => Value::test_int(2024),
+                    "fiscal_quarter" => Value::test_int(3),
+                    "fiscal_period" => Value::test_int(7),
+                    "fiscal_week" => Value::test_int(30),
+                    "start" => Value::test_string("2024-08-04T00:00:00-05:00[America/Chicago]"),
+                    "end" => Value::test_string("2024-11-02T23:59:59.999999999-05:00[America/Chicago]"),
+                })),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let unit: Spanned<String> = call.req(0)?;
+        let fiscal = get_fiscal_calendar_from_call(call)?;
+        let unit_kind = get_fiscal_unit_from_string(unit)?;
+
+        let datetime = get_zoned_from_value(input, &local_tz)?;
+        let fiscal_date = fiscal.locate(datetime.date())?;
+        let (start, end) = fiscal.period_bounds(&fiscal_date, unit_kind)?;
+
+        let time_zone = datetime.time_zone().clone();
+        let start = start
+            .to_zoned(time_zone.clone())
+            .and_then(|zdt| zdt.start_of_day())
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        let end = end
+            .to_zoned(time_zone)
+            .and_then(|zdt| zdt.start_of_day())
+            .and_then(|zdt| zdt.checked_sub(1.nanosecond()))
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        Ok(Value::record(
+            record! {
+                "fiscal_year" => Value::int(fiscal_date.year.into(), call.head),
+                "fiscal_quarter" => Value::int(fiscal_date.quarter.into(), call.head),
+                "fiscal_period" => Value::int(fiscal_date.period.into(), call.head),
+                "fiscal_week" => Value::int(fiscal_date.week.into(), call.head),
+                "start" => Value::string(start.to_string(), call.head),
+                "end" => Value::string(end.to_string(), call.head),
+            },
+            call.head,
+        ))
+    }
+}
+
+// Which calendar year a fiscal year is named after
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FiscalYearLabel {
+    Start,
+    End,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FiscalUnit {
+    Year,
+    Quarter,
+    Period,
+    Week,
+}
+
+// A fiscal calendar is either calendar months counted from a start month, or a
+// week based retail calendar whose quarters are 13 weeks split by a pattern like
+// 4-4-5 and whose year ends on a weekday at the end of the month before the start
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiscalCalendar {
+    pub start_month: i8,
+    pub label: FiscalYearLabel,
+    pub week_pattern: Option<[i8; 3]>,
+    pub end_day: Weekday,
+    pub nearest: bool,
+}
+
+impl Default for FiscalCalendar {
+    fn default() -> Self {
+        FiscalCalendar {
+            start_month: 1,
+            label: FiscalYearLabel::End,
+            week_pattern: None,
+            end_day: Weekday::Saturday,
+            nearest: false,
+        }
+    }
+}
+
+// Where a date falls in its fiscal year
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiscalDate {
+    pub year: i16,
+    pub quarter: i8,
+    pub period: i8,
+    pub week: i8,
+    year_start: civil::Date,
+    next_year_start: civil::Date,
+}
+
+impl FiscalCalendar {
+    // The first day of the fiscal year that ends in a calendar year
+    fn year_start(&self, end_year: i16) -> Result<civil::Date, LabeledError> {
+        let start = if self.week_pattern.is_some() {
+            self.week_year_end(end_year - 1)
+                .and_then(|end| end.tomorrow())
+        } else if self.start_month == 1 {
+            civil::Date::new(end_year, 1, 1)
+        } else {
+            civil::Date::new(end_year - 1, self.start_month, 1)
+        };
+        start.map_err(|err| LabeledError::new(err.to_string()))
+    }
+
+    // The last day of a week based fiscal year that ends in a calendar year
+    fn week_year_end(&self, end_year: i16) -> Result<civil::Date, jiff::Error> {
+        let end_month = (self.start_month - 2).rem_euclid(12) + 1;
+        let last_of_month = civil::Date::new(end_year, end_month, 1)?.last_of_month();
+        let last_end_day = if last_of_month.weekday() == self.end_day {
+            last_of_month
+        } else {
+            last_of_month.nth_weekday(-1, self.end_day)?
+        };
+        // The nearest end day is at most 3 days after the end of the month
+        if self.nearest && last_of_month.since(last_end_day)?.get_days() > 3 {
+            last_end_day.checked_add(1.week())
+        } else {
+            Ok(last_end_day)
+        }
+    }
+
+    pub fn locate(&self, date: civil::Date) -> Result<FiscalDate, LabeledError> {
+        // Fiscal years are found by the calendar year they end in
+        let mut end_year = if self.start_month > 1 && date.month() >= self.start_month {
+            date.year() + 1
+        } else {
+            date.year()
+        };
+        while date < self.year_start(end_year)? {
+            end_year -= 1;
+        }
+        while date >= self.year_start(end_year + 1)? {
+            end_year += 1;
+        }
+        let year_start = self.year_start(end_year)?;
+        let next_year_start = self.year_start(end_year + 1)?;
+
+        let days = date
+            .since(year_start)
+            .map_err(|err| LabeledError::new(err.to_string()))?
+            .get_days();
+        let week = (days / 7 + 1) as i8;
+        let period = match self.week_pattern {
+            Some(pattern) => {
+                let mut weeks_before = 0;
+                let mut period = 12;
+                for (index, weeks) in pattern.iter().cycle().take(12).enumerate() {
+                    weeks_before += weeks;
+                    if week <= weeks_before {
+                        period = index as i8 + 1;
+                        break;
+                    }
+                }
+                period
+            }
+            None => (date.month() - self.start_month).rem_euclid(12) + 1,
+        };
+        let year = match self.label {
+            FiscalYearLabel::Start if self.start_month > 1 => end_year - 1,
+            _ => end_year,
+        };
+        Ok(FiscalDate {
+            year,
+            quarter: (period - 1) / 3 + 1,
+            period,
+            week,
+            year_start,
+            next_year_start,
+        })
+    }
+
+    // Return the first day of the fiscal period containing the fiscal date and the
+    // first day after it
+    pub fn period_bounds(
+        &self,
+        fiscal_date: &FiscalDate,
+        unit: FiscalUnit,
+    ) -> Result<(civil::Date, civil::Date), LabeledError> {
+        let year_start = fiscal_date.year_start;
+        let offset = |span: jiff::Span| {
+            year_start
+                .checked_add(span)
+                .map_err(|err| LabeledError::new(err.to_string()))
+        };
+        // Weeks before each period starts, with the 53rd week left in the last period
+        let weeks_before_period = |period: i8| -> i64 {
+            self.week_pattern.map_or(0, |pattern| {
+                pattern
+                    .iter()
+                    .cycle()
+                    .take(period as usize - 1)
+                    .map(|weeks| i64::from(*weeks))
+                    .sum()
+            })
+        };
+        let (start, end) = match (unit, self.week_pattern) {
+            (FiscalUnit::Year, _) => (year_start, fiscal_date.next_year_start),
+            (FiscalUnit::Week, _) => {
+                let start = offset(i64::from(fiscal_date.week - 1).weeks())?;
+                let end = offset(i64::from(fiscal_date.week).weeks())?;
+                (start, end)
+            }
+            (FiscalUnit::Quarter, None) => {
+                let start = offset(i64::from((fiscal_date.quarter - 1) * 3).months())?;
+                let end = offset(i64::from(fiscal_date.quarter * 3).months())?;
+                (start, end)
+            }
+            (FiscalUnit::Period, None) => {
+                let start = offset(i64::from(fiscal_date.period - 1).months())?;
+                let end = offset(i64::from(fiscal_date.period).months())?;
+                (start, end)
+            }
+            (FiscalUnit::Quarter, Some(_)) => {
+                let first_period = (fiscal_date.quarter - 1) * 3 + 1;
+                let start = offset(weeks_before_period(first_period).weeks())?;
+                let end = offset(weeks_before_period(first_period + 3).weeks())?;
+                (start, end)
+            }
+            (FiscalUnit::Period, Some(_)) => {
+                let start = offset(weeks_before_period(fiscal_date.period).weeks())?;
+                let end = offset(weeks_before_period(fiscal_date.period + 1).weeks())?;
+                (start, end)
+            }
+        };
+        // The last quarter and period take a 53rd week, the last week of a month
+        // based year is cut short at the end of the year
+        let is_last = match unit {
+            FiscalUnit::Year => true,
+            FiscalUnit::Quarter => fiscal_date.quarter == 4,
+            FiscalUnit::Period => fiscal_date.period == 12,
+            FiscalUnit::Week => false,
+        };
+        let end = if is_last || end > fiscal_date.next_year_start {
+            fiscal_date.next_year_start
+        } else {
+            end
+        };
+        Ok((start, end))
+    }
+}
+
+// Add the flags shared by commands that work with fiscal calendars
+pub fn fiscal_calendar_flags(signature: Signature) -> Signature {
+    signature
+        .named(
+            "fiscal-start",
+            SyntaxShape::Int,
+            "Month number (1-12) the fiscal year starts in (default 1).",
+            Some('f'),
+        )
+        .named(
+            "fiscal-label",
+            SyntaxShape::String,
+            "Name fiscal years after the calendar year they start or end (default) in.",
+            None,
+        )
+        .named(
+            "fiscal-pattern",
+            SyntaxShape::String,
+            "Weeks in each period of a quarter for week based calendars: 4-4-5, 4-5-4 or 5-4-4.",
+            None,
+        )
+        .named(
+            "fiscal-end-day",
+            SyntaxShape::String,
+            "Weekday week based fiscal years end on (default saturday).",
+            None,
+        )
+        .switch(
+            "fiscal-nearest",
+            "End week based fiscal years on the end day nearest the end of the month instead of the last one in it",
+            None,
+        )
+}
+
+pub fn get_fiscal_calendar_from_call(call: &EvaluatedCall) -> Result<FiscalCalendar, LabeledError> {
+    let start: Option<Spanned<i64>> = call.get_flag("fiscal-start")?;
+    let label: Option<Spanned<String>> = call.get_flag("fiscal-label")?;
+    let pattern: Option<Spanned<String>> = call.get_flag("fiscal-pattern")?;
+    let end_day: Option<Spanned<String>> = call.get_flag("fiscal-end-day")?;
+    let nearest = call.has_flag("fiscal-nearest")?;
+
+    let start_month = match start {
+        Some(month) if !(1..=12).contains(&month.item) => {
+            return Err(
+                LabeledError::new("fiscal-start must be a month number from 1 to 12")
+                    .with_label(format!("got {}", month.item), month.span),
+            );
+        }
+        Some(month) => month.item as i8,
+        None => 1,
+    };
+    let label = match label {
+        None => FiscalYearLabel::End,
+        Some(label) => match label.item.to_lowercase().as_ref() {
+            "start" => FiscalYearLabel::Start,
+            "end" => FiscalYearLabel::End,
+            _ => {
+                return Err(
+                    LabeledError::new(format!("Invalid fiscal label: {}", label.item))
+                        .with_label("expected start or end", label.span),
+                );
+            }
+        },
+    };
+    let week_pattern = match pattern {
+        None => None,
+        Some(pattern) => match pattern.item.as_ref() {
+            "4-4-5" | "445" => Some([4, 4, 5]),
+            "4-5-4" | "454" => Some([4, 5, 4]),
+            "5-4-4" | "544" => Some([5, 4, 4]),
+            _ => {
+                return Err(
+                    LabeledError::new(format!("Invalid fiscal pattern: {}", pattern.item))
+                        .with_label("expected 4-4-5, 4-5-4 or 5-4-4", pattern.span),
+                );
+            }
+        },
+    };
+    let end_day = match end_day {
+        Some(end_day) => get_weekday_from_string(end_day)?,
+        None => Weekday::Saturday,
+    };
+    Ok(FiscalCalendar {
+        start_month,
+        label,
+        week_pattern,
+        end_day,
+        nearest,
+    })
+}
+
+fn get_fiscal_unit_from_string(unit: Spanned<String>) -> Result<FiscalUnit, LabeledError> {
+    match unit.item.to_lowercase().as_ref() {
+        "year" | "years" | "yr" | "fy" | "fiscal_year" => Ok(FiscalUnit::Year),
+        "fq" | "fiscal_quarter" => Ok(FiscalUnit::Quarter),
+        name if is_quarter_unit_string(name) => Ok(FiscalUnit::Quarter),
+        "period" | "periods" | "month" | "months" | "fp" | "fiscal_period" => {
+            Ok(FiscalUnit::Period)
+        }
+        "week" | "weeks" | "wk" | "fw" | "fiscal_week" => Ok(FiscalUnit::Week),
+        _ => Err(
+            LabeledError::new(format!("Invalid fiscal unit: {}", unit.item))
+                .with_label("expected year, quarter, period or week", unit.span),
+        ),
+    }
+}
+
+// Return a fiscal part of a datetime for dt part
+pub fn get_fiscal_part_from_zoned(
+    part: FiscalUnit,
+    datetime: &Zoned,
+    fiscal: &FiscalCalendar,
+) -> Result<i16, LabeledError> {
+    let fiscal_date = fiscal.locate(datetime.date())?;
+    Ok(match part {
+        FiscalUnit::Year => fiscal_date.year,
+        FiscalUnit::Quarter => fiscal_date.quarter.into(),
+        FiscalUnit::Period => fiscal_date.period.into(),
+        FiscalUnit::Week => fiscal_date.week.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    fn retail_calendar() -> FiscalCalendar {
+        FiscalCalendar {
+            start_month: 2,
+            label: FiscalYearLabel::Start,
+            week_pattern: Some([4, 4, 5]),
+            end_day: Weekday::Saturday,
+            nearest: true,
+        }
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtFiscalPeriod)
+    }
+
+    #[test]
+    fn test_retail_calendar_53_week_year() {
+        let fiscal = retail_calendar();
+        // Fiscal 2023 ran from 2023-01-29 to 2024-02-03 with 53 weeks
+        let last_day = fiscal.locate(date(2024, 2, 3)).unwrap();
+        assert_eq!(last_day.year, 2023);
+        assert_eq!(last_day.week, 53);
+        assert_eq!((last_day.quarter, last_day.period), (4, 12));
+        let (start, end) = fiscal.period_bounds(&last_day, FiscalUnit::Year).unwrap();
+        assert_eq!(end.since(start).unwrap().get_days(), 371);
+        let (start, end) = fiscal.period_bounds(&last_day, FiscalUnit::Period).unwrap();
+        assert_eq!((start, end), (date(2023, 12, 24), date(2024, 2, 4)));
+
+        let first_day = fiscal.locate(date(2024, 2, 4)).unwrap();
+        assert_eq!(
+            (first_day.year, first_day.week, first_day.period),
+            (2024, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_last_weekday_year_end() {
+        let fiscal = FiscalCalendar {
+            start_month: 7,
+            week_pattern: Some([5, 4, 4]),
+            ..FiscalCalendar::default()
+        };
+        // The last Saturday of June 2024 is the 29th
+        let fiscal_date = fiscal.locate(date(2024, 6, 30)).unwrap();
+        assert_eq!(fiscal_date.year, 2025);
+        assert_eq!((fiscal_date.week, fiscal_date.period), (1, 1));
+        let (start, end) = fiscal
+            .period_bounds(&fiscal_date, FiscalUnit::Period)
+            .unwrap();
+        assert_eq!((start, end), (date(2024, 6, 30), date(2024, 8, 4)));
+    }
+
+    #[test]
+    fn test_month_calendar_labels() {
+        let mut fiscal = FiscalCalendar {
+            start_month: 4,
+            ..FiscalCalendar::default()
+        };
+        let fiscal_date = fiscal.locate(date(2025, 3, 31)).unwrap();
+        assert_eq!(fiscal_date.year, 2025);
+        assert_eq!((fiscal_date.quarter, fiscal_date.period), (4, 12));
+        fiscal.label = FiscalYearLabel::Start;
+        assert_eq!(fiscal.locate(date(2025, 3, 31)).unwrap().year, 2024);
+        assert_eq!(fiscal.locate(date(2025, 4, 1)).unwrap().year, 2025);
+    }
+}
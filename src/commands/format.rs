@@ -1,3 +1,4 @@
+use super::tzdb::local_time_zone;
 use super::utils::{
     parse_datetime_string_add_nanos_optionally, unix_timestamp_in_seconds_to_local_zoned,
 };
@@ -51,10 +52,11 @@ impl SimplePluginCommand for DtFormat {
     fn run(
         &self,
         _plugin: &DtPlugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
         let span = input.span();
         let list = call.has_flag("list")?;
         if list {
@@ -74,16 +76,22 @@ impl SimplePluginCommand for DtFormat {
         let datetime = match input {
             Value::Date { val, .. } => {
                 // so much easier just to output chrono as rfc 3339 and let jiff parse it
-                parse_datetime_string_add_nanos_optionally(&val.to_rfc3339(), None, span, None)?
+                parse_datetime_string_add_nanos_optionally(
+                    &val.to_rfc3339(),
+                    None,
+                    span,
+                    None,
+                    &local_tz,
+                )?
             }
             Value::String { val, .. } => {
                 // eprintln!("String: {:?}", val);
-                parse_datetime_string_add_nanos_optionally(val, None, span, None)?
+                parse_datetime_string_add_nanos_optionally(val, None, span, None, &local_tz)?
             }
             Value::Int { val, .. } => {
                 // eprintln!("Int: {:?}", val);
                 let dt_str = unix_timestamp_in_seconds_to_local_zoned(*val)?;
-                parse_datetime_string_add_nanos_optionally(&dt_str, None, span, None)?
+                parse_datetime_string_add_nanos_optionally(&dt_str, None, span, None, &local_tz)?
             }
             _ => {
                 return Err(LabeledError::new(
@@ -0,0 +1,217 @@
+use super::timezones::{available_time_zone_names, get_time_zone_links_for_canonical};
+use super::tzdb::{local_time_zone, time_zone_database};
+use super::utils::{get_plugin_config_strings, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::tz::Dst;
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+// Plugin config key with the regions `dt guess-zone` ranks first
+const PREFERRED_REGIONS_KEY: &str = "preferred_regions";
+
+pub struct DtGuessZone;
+
+impl SimplePluginCommand for DtGuessZone {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt guess-zone"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "prefer",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Regions or zones to rank first, in order, e.g. [Europe America/Chicago] (defaults to preferred_regions in the plugin config).",
+                Some('p'),
+            )
+            .switch(
+                "canonical",
+                "Only list canonical zones, leaving out aliases like US/Central",
+                Some('c'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Guess the IANA time zones a datetime with only a UTC offset could be from, listing the zones that had that offset at that instant"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Zones in preferred regions come first in the order the regions are given, then the rest by name. A region matches a zone with that name or a zone below it, so Europe matches Europe/Berlin. Without --prefer the list in $env.config.plugins.dt.preferred_regions is used, e.g. $env.config.plugins.dt = { preferred_regions: [America Europe] }. The datetime column is the piped in instant in each zone, ready for DST aware arithmetic."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "time zone",
+            "offset",
+            "guess",
+            "infer",
+            "candidates",
+            "legacy",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30+05:45' | dt guess-zone",
+                description: "Find the zones at +05:45 at an instant",
+                result: None,
+            },
+            Example {
+                example: "2024-08-25T10:52:30-05:00 | dt guess-zone --prefer [America/Chicago America] --canonical",
+                description: "Rank the zones a nushell datetime could be from, preferring Chicago then the rest of the Americas",
+                result: None,
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00' | dt guess-zone | get 0.datetime | dt add 3mo",
+                description: "Add months to a datetime in the most likely zone so DST changes are applied",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let preferences: Option<Vec<Spanned<String>>> = call.get_flag("prefer")?;
+        let canonical_only = call.has_flag("canonical")?;
+        let preferences = match preferences {
+            Some(preferences) => preferences,
+            None => get_plugin_config_strings(engine, PREFERRED_REGIONS_KEY)?.unwrap_or_default(),
+        };
+        let preferences: Vec<String> = preferences
+            .into_iter()
+            .map(|preference| preference.item)
+            .collect();
+
+        let datetime = get_zoned_from_value(input, &local_tz)?;
+        let timestamp = datetime.timestamp();
+        let offset = datetime.offset();
+        let links = if canonical_only {
+            Some(get_time_zone_links_for_canonical(call.head)?)
+        } else {
+            None
+        };
+
+        let mut candidates = vec![];
+        for name in available_time_zone_names() {
+            if links
+                .as_ref()
+                .is_some_and(|links| links.contains_key(&name))
+            {
+                continue;
+            }
+            let Ok(time_zone) = time_zone_database().get(&name) else {
+                continue;
+            };
+            let info = time_zone.to_offset_info(timestamp);
+            if info.offset() != offset {
+                continue;
+            }
+            let rank = preference_rank(&name, &preferences);
+            let row = Value::record(
+                record! {
+                    "zone" => Value::string(&name, call.head),
+                    "abbreviation" => Value::string(info.abbreviation(), call.head),
+                    "dst" => Value::bool(info.dst() == Dst::Yes, call.head),
+                    "preferred" => Value::bool(rank.is_some(), call.head),
+                    "datetime" => Value::string(
+                        timestamp.to_zoned(time_zone.clone()).to_string(),
+                        call.head,
+                    ),
+                },
+                call.head,
+            );
+            candidates.push((rank, row));
+        }
+        // Preferred zones first, then by name, which available_time_zone_names sorts by
+        candidates.sort_by_key(|(rank, _)| rank.unwrap_or(usize::MAX));
+        Ok(Value::list(
+            candidates.into_iter().map(|(_, row)| row).collect(),
+            call.head,
+        ))
+    }
+}
+
+// The position of the first preference matching a zone, either the zone itself or
+// a region it's in
+fn preference_rank(zone: &str, preferences: &[String]) -> Option<usize> {
+    preferences.iter().position(|preference| {
+        let preference = preference.trim_end_matches('/');
+        let (region, rest) = zone
+            .split_at_checked(preference.len())
+            .unwrap_or((zone, ""));
+        region.eq_ignore_ascii_case(preference) && (rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtGuessZone)
+    }
+
+    #[test]
+    fn test_preference_rank() {
+        let preferences = vec!["America/Chicago".to_string(), "europe".to_string()];
+        assert_eq!(preference_rank("America/Chicago", &preferences), Some(0));
+        assert_eq!(preference_rank("Europe/Berlin", &preferences), Some(1));
+        assert_eq!(preference_rank("America/New_York", &preferences), None);
+        assert_eq!(preference_rank("EuropeX/Berlin", &preferences), None);
+        assert_eq!(preference_rank("Europe/Berlin", &[]), None);
+    }
+
+    #[test]
+    fn test_guess_zone_finds_kathmandu() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        let zones = plugin_test
+            .eval("'2024-08-25T10:52:30+05:45' | dt guess-zone")?
+            .into_value(nu_protocol::Span::test_data())?
+            .into_list()?;
+        let kathmandu = zones
+            .iter()
+            .find(|row| row.get_data_by_key("zone") == Some(Value::test_string("Asia/Kathmandu")))
+            .expect("Asia/Kathmandu is at +05:45");
+        assert_eq!(
+            kathmandu.get_data_by_key("datetime"),
+            Some(Value::test_string(
+                "2024-08-25T10:52:30+05:45[Asia/Kathmandu]"
+            ))
+        );
+        assert_eq!(
+            kathmandu.get_data_by_key("dst"),
+            Some(Value::test_bool(false))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_preferred_zones_come_first() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        let zones = plugin_test
+            .eval("'2024-08-25T10:52:30+05:30' | dt guess-zone --prefer [Asia/Colombo]")?
+            .into_value(nu_protocol::Span::test_data())?
+            .into_list()?;
+        let names: Vec<Value> = zones
+            .iter()
+            .filter_map(|row| row.get_data_by_key("zone"))
+            .collect();
+        assert_eq!(names.first(), Some(&Value::test_string("Asia/Colombo")));
+        assert!(names.contains(&Value::test_string("Asia/Kolkata")));
+        Ok(())
+    }
+}
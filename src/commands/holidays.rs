@@ -0,0 +1,793 @@
+use super::easter::{orthodox_easter, western_easter};
+use super::tzdb::{local_now, local_time_zone};
+use super::utils::{get_weekday_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{ToSpan, civil, civil::Weekday, tz::TimeZone};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, PipelineData, Record, Signature, Spanned, SyntaxShape, Value,
+    record,
+};
+
+const BUILTIN_CALENDARS: [&str; 5] = ["us", "nyse", "uk", "target", "de"];
+
+pub struct DtHolidays;
+
+impl SimplePluginCommand for DtHolidays {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt holidays"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "calendar",
+                SyntaxShape::String,
+                "Built-in calendar name (us, nyse, uk, target or de) or a path to a NUON or TOML calendar file.",
+            )
+            .named(
+                "year",
+                SyntaxShape::Int,
+                "Year to list holidays for (defaults to the current year).",
+                Some('y'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "List the holidays of a calendar in a year with the dates they are observed on"
+    }
+
+    fn extra_description(&self) -> &str {
+        HOLIDAY_CALENDAR_EXTRA_DESCRIPTION
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "holiday", "calendar", "bank", "market", "closed"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        let holiday = |name: &str, date: &str, weekday: &str| {
+            Value::test_record(record! {
+                "name" => Value::test_string(name),
+                "date" => Value::test_string(date),
+                "observed" => Value::test_string(date),
+                "weekday" => Value::test_string(weekday),
+            })
+        };
+        vec![
+            Example {
+                example: "dt holidays target --year 2026",
+                description: "List the TARGET2 payment system holidays in 2026",
+                result: Some(Value::test_list(vec![
+                    holiday("New Year's Day", "2026-01-01", "Thursday"),
+                    holiday("Good Friday", "2026-04-03", "Friday"),
+                    holiday("Easter Monday", "2026-04-06", "Monday"),
+                    holiday("Labour Day", "2026-05-01", "Friday"),
+                    holiday("Christmas Day", "2026-12-25", "Friday"),
+                    holiday("Boxing Day", "2026-12-26", "Saturday"),
+                ])),
+            },
+            Example {
+                example: "dt holidays ./company.nuon --year 2026",
+                description: "List the holidays of a calendar loaded from a NUON file",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let calendar_name: Spanned<String> = call.req(0)?;
+        let year: Option<Spanned<i64>> = call.get_flag("year")?;
+
+        let year = match year {
+            Some(year) => i16::try_from(year.item)
+                .ok()
+                .filter(|year| (-9998..=9998).contains(year))
+                .ok_or_else(|| {
+                    LabeledError::new("year is out of range")
+                        .with_label(format!("got {}", year.item), year.span)
+                })?,
+            None => local_now(engine).year(),
+        };
+        let calendar = load_holiday_calendar(engine, calendar_name)?;
+
+        let span = call.head;
+        let holidays = calendar
+            .holidays_in_year(year)
+            .into_iter()
+            .map(|holiday| {
+                Value::record(
+                    record! {
+                        "name" => Value::string(holiday.name, span),
+                        "date" => Value::string(holiday.date.to_string(), span),
+                        "observed" => Value::string(holiday.observed.to_string(), span),
+                        "weekday" => Value::string(format!("{:?}", holiday.observed.weekday()), span),
+                    },
+                    span,
+                )
+            })
+            .collect();
+        Ok(Value::list(holidays, span))
+    }
+}
+
+pub struct DtIsHoliday;
+
+impl SimplePluginCommand for DtIsHoliday {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt is-holiday"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "calendar",
+                SyntaxShape::String,
+                "Built-in calendar name (us, nyse, uk, target or de) or a path to a NUON or TOML calendar file.",
+            )
+            .switch(
+                "observed",
+                "Only match the dates holidays are observed on, not weekend dates they moved from",
+                Some('o'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return whether the provided date or datetime falls on a holiday of a calendar"
+    }
+
+    fn extra_description(&self) -> &str {
+        HOLIDAY_CALENDAR_EXTRA_DESCRIPTION
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "holiday", "calendar", "bank", "market", "closed"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2026-11-26T12:00:00-05:00[America/New_York]' | dt is-holiday us",
+                description: "Check whether a datetime falls on a US federal holiday",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: "'2027-07-05T10:00:00-04:00[America/New_York]' | dt is-holiday nyse",
+                description: "Independence Day falls on a Sunday in 2027 so the market is closed on Monday",
+                result: Some(Value::test_bool(true)),
+            },
+            Example {
+                example: "'2027-07-04T10:00:00-04:00[America/New_York]' | dt is-holiday nyse --observed",
+                description: "Only match the observed date",
+                result: Some(Value::test_bool(false)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let calendar_name: Spanned<String> = call.req(0)?;
+        let observed_only = call.has_flag("observed")?;
+
+        let calendar = load_holiday_calendar(engine, calendar_name)?;
+        let date = get_zoned_from_value(input, &local_tz)?.date();
+        let is_holiday = calendar
+            .holidays_around(date)
+            .iter()
+            .any(|holiday| holiday.observed == date || (!observed_only && holiday.date == date));
+        Ok(Value::bool(is_holiday, call.head))
+    }
+}
+
+const HOLIDAY_CALENDAR_EXTRA_DESCRIPTION: &str = r#"Built-in calendars cover regular rules only, one-off closures aren't included:
+  us      US federal holidays
+  nyse    New York Stock Exchange
+  uk      England and Wales bank holidays
+  target  TARGET2 euro payment system
+  de      German national holidays
+
+Calendar files are records with an optional name, weekend list and a built-in calendar to extend, plus a list of holidays. Each holiday has a name and one of date (a one-off date), month and day, month, weekday and nth (-1 for the last), easter (days from Easter Sunday) or orthodox_easter. Holidays can also set observed (none, nearest, sunday-to-monday or next-weekday), since and until.
+  {name: acme, extends: us, holidays: [{name: "Founders Day", month: 3, day: 14, observed: nearest}, {name: "Summer Friday", month: 8, weekday: fri, nth: -1}]}"#;
+
+// How a holiday that falls on a weekend is moved to a working day
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Observance {
+    // Stay on the weekend
+    None,
+    // Move to the closest working day, e.g. Saturday to Friday and Sunday to Monday
+    Nearest,
+    // Only Sunday moves forward to the next working day
+    SundayToMonday,
+    // Move forward to the next working day that isn't already a holiday
+    NextWeekday,
+}
+
+#[derive(Clone, Debug)]
+pub enum HolidayDate {
+    Fixed {
+        month: i8,
+        day: i8,
+    },
+    Once(civil::Date),
+    // Negative nth counts back from the end of the month, -1 is the last
+    NthWeekday {
+        month: i8,
+        weekday: Weekday,
+        nth: i8,
+    },
+    // Days from Western Easter Sunday
+    Easter {
+        offset: i16,
+    },
+    // Days from Orthodox Easter Sunday
+    OrthodoxEaster {
+        offset: i16,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct HolidayRule {
+    pub name: String,
+    pub date: HolidayDate,
+    pub observance: Observance,
+    pub since: Option<i16>,
+    pub until: Option<i16>,
+}
+
+impl HolidayRule {
+    fn new(name: &str, date: HolidayDate, observance: Observance) -> HolidayRule {
+        HolidayRule {
+            name: name.to_string(),
+            date,
+            observance,
+            since: None,
+            until: None,
+        }
+    }
+
+    fn since(mut self, year: i16) -> HolidayRule {
+        self.since = Some(year);
+        self
+    }
+
+    // The actual date of the holiday in a year, if it happens that year
+    fn date_in_year(&self, year: i16) -> Option<civil::Date> {
+        if self.since.is_some_and(|since| year < since)
+            || self.until.is_some_and(|until| year > until)
+        {
+            return None;
+        }
+        match &self.date {
+            HolidayDate::Fixed { month, day } => civil::Date::new(year, *month, *day).ok(),
+            HolidayDate::Once(date) => (date.year() == year).then_some(*date),
+            HolidayDate::NthWeekday {
+                month,
+                weekday,
+                nth,
+            } => civil::Date::new(year, *month, 1)
+                .and_then(|first| first.nth_weekday_of_month(*nth, *weekday))
+                .ok(),
+            HolidayDate::Easter { offset } => western_easter(year)
+                .ok()
+                .and_then(|easter| easter.checked_add(i64::from(*offset).days()).ok()),
+            HolidayDate::OrthodoxEaster { offset } => orthodox_easter(year)
+                .ok()
+                .and_then(|easter| easter.checked_add(i64::from(*offset).days()).ok()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Holiday {
+    pub name: String,
+    pub date: civil::Date,
+    pub observed: civil::Date,
+}
+
+#[derive(Clone, Debug)]
+pub struct HolidayCalendar {
+    pub name: String,
+    pub weekend: Vec<Weekday>,
+    pub rules: Vec<HolidayRule>,
+}
+
+impl HolidayCalendar {
+    fn is_weekend(&self, date: civil::Date) -> bool {
+        self.weekend.contains(&date.weekday())
+    }
+
+    // The holidays whose rules fall in a year, sorted by date. Observed dates can
+    // land in the previous or next year, e.g. a Saturday January 1st observed on
+    // the Friday before.
+    pub fn holidays_in_year(&self, year: i16) -> Vec<Holiday> {
+        let dated: Vec<(&HolidayRule, civil::Date)> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.date_in_year(year).map(|date| (rule, date)))
+            .collect();
+
+        // Holidays already on working days are placed first so next-weekday
+        // observance can skip past them, e.g. Christmas on a Sunday is observed on
+        // the Tuesday when Boxing Day is on the Monday
+        let mut holidays: Vec<Holiday> = vec![];
+        for (rule, date) in dated.iter().filter(|(_, date)| !self.is_weekend(*date)) {
+            holidays.push(Holiday {
+                name: rule.name.clone(),
+                date: *date,
+                observed: *date,
+            });
+        }
+        for (rule, date) in dated.iter().filter(|(_, date)| self.is_weekend(*date)) {
+            let observed = self.observe(*date, rule.observance, &holidays);
+            holidays.push(Holiday {
+                name: rule.name.clone(),
+                date: *date,
+                observed,
+            });
+        }
+        holidays.sort_by_key(|holiday| (holiday.date, holiday.observed));
+        holidays
+    }
+
+    // Holidays from the year before to the year after a date, so observed dates
+    // that crossed a year boundary are included
+    pub fn holidays_around(&self, date: civil::Date) -> Vec<Holiday> {
+        (date.year().saturating_sub(1)..=date.year().saturating_add(1))
+            .flat_map(|year| self.holidays_in_year(year))
+            .collect()
+    }
+
+    fn observe(&self, date: civil::Date, observance: Observance, taken: &[Holiday]) -> civil::Date {
+        let working_day = |step: i64, skip_taken: bool| {
+            date.series(step.days())
+                .skip(1)
+                .take(14)
+                .find(|day| {
+                    let is_taken =
+                        skip_taken && taken.iter().any(|holiday| holiday.observed == *day);
+                    !self.is_weekend(*day) && !is_taken
+                })
+                .unwrap_or(date)
+        };
+        match observance {
+            Observance::None => date,
+            Observance::Nearest => {
+                let before = working_day(-1, false);
+                let after = working_day(1, false);
+                if date
+                    .since(before)
+                    .map(|span| span.get_days())
+                    .unwrap_or(i32::MAX)
+                    < after
+                        .since(date)
+                        .map(|span| span.get_days())
+                        .unwrap_or(i32::MAX)
+                {
+                    before
+                } else {
+                    after
+                }
+            }
+            Observance::SundayToMonday if date.weekday() == Weekday::Sunday => {
+                working_day(1, false)
+            }
+            Observance::SundayToMonday => date,
+            Observance::NextWeekday => working_day(1, true),
+        }
+    }
+}
+
+pub fn get_builtin_holiday_calendar(name: &str) -> Option<HolidayCalendar> {
+    use HolidayDate::{Easter, Fixed, NthWeekday};
+    use Observance::{Nearest, NextWeekday, SundayToMonday};
+    use Weekday::{Monday, Thursday};
+
+    let fixed = |name: &str, month: i8, day: i8, observance: Observance| {
+        HolidayRule::new(name, Fixed { month, day }, observance)
+    };
+    let nth = |name: &str, month: i8, weekday: Weekday, nth: i8| {
+        HolidayRule::new(
+            name,
+            NthWeekday {
+                month,
+                weekday,
+                nth,
+            },
+            Observance::None,
+        )
+    };
+    let easter =
+        |name: &str, offset: i16| HolidayRule::new(name, Easter { offset }, Observance::None);
+
+    let rules = match name.to_lowercase().as_str() {
+        "us" => vec![
+            fixed("New Year's Day", 1, 1, Nearest),
+            nth("Martin Luther King Jr. Day", 1, Monday, 3).since(1986),
+            nth("Washington's Birthday", 2, Monday, 3),
+            nth("Memorial Day", 5, Monday, -1),
+            fixed("Juneteenth", 6, 19, Nearest).since(2021),
+            fixed("Independence Day", 7, 4, Nearest),
+            nth("Labor Day", 9, Monday, 1),
+            nth("Columbus Day", 10, Monday, 2),
+            fixed("Veterans Day", 11, 11, Nearest),
+            nth("Thanksgiving Day", 11, Thursday, 4),
+            fixed("Christmas Day", 12, 25, Nearest),
+        ],
+        // The exchange doesn't close on the Friday before a Saturday New Year's Day
+        "nyse" => vec![
+            fixed("New Year's Day", 1, 1, SundayToMonday),
+            nth("Martin Luther King Jr. Day", 1, Monday, 3).since(1998),
+            nth("Washington's Birthday", 2, Monday, 3),
+            easter("Good Friday", -2),
+            nth("Memorial Day", 5, Monday, -1),
+            fixed("Juneteenth", 6, 19, Nearest).since(2022),
+            fixed("Independence Day", 7, 4, Nearest),
+            nth("Labor Day", 9, Monday, 1),
+            nth("Thanksgiving Day", 11, Thursday, 4),
+            fixed("Christmas Day", 12, 25, Nearest),
+        ],
+        "uk" | "gb" => vec![
+            fixed("New Year's Day", 1, 1, NextWeekday),
+            easter("Good Friday", -2),
+            easter("Easter Monday", 1),
+            nth("Early May Bank Holiday", 5, Monday, 1),
+            nth("Spring Bank Holiday", 5, Monday, -1),
+            nth("Summer Bank Holiday", 8, Monday, -1),
+            fixed("Christmas Day", 12, 25, NextWeekday),
+            fixed("Boxing Day", 12, 26, NextWeekday),
+        ],
+        "target" => vec![
+            fixed("New Year's Day", 1, 1, Observance::None),
+            easter("Good Friday", -2),
+            easter("Easter Monday", 1),
+            fixed("Labour Day", 5, 1, Observance::None),
+            fixed("Christmas Day", 12, 25, Observance::None),
+            fixed("Boxing Day", 12, 26, Observance::None),
+        ],
+        "de" => vec![
+            fixed("Neujahr", 1, 1, Observance::None),
+            easter("Karfreitag", -2),
+            easter("Ostermontag", 1),
+            fixed("Tag der Arbeit", 5, 1, Observance::None),
+            easter("Christi Himmelfahrt", 39),
+            easter("Pfingstmontag", 50),
+            fixed("Tag der Deutschen Einheit", 10, 3, Observance::None).since(1990),
+            fixed("1. Weihnachtstag", 12, 25, Observance::None),
+            fixed("2. Weihnachtstag", 12, 26, Observance::None),
+        ],
+        _ => return None,
+    };
+    Some(HolidayCalendar {
+        name: name.to_lowercase(),
+        weekend: vec![Weekday::Saturday, Weekday::Sunday],
+        rules,
+    })
+}
+
+// Find a built-in calendar by name or load a calendar file with nushell's `open`
+// command, so any format nushell can open into a record works
+pub fn load_holiday_calendar(
+    engine: &EngineInterface,
+    calendar: Spanned<String>,
+) -> Result<HolidayCalendar, LabeledError> {
+    if let Some(builtin) = get_builtin_holiday_calendar(&calendar.item) {
+        return Ok(builtin);
+    }
+    let is_file = std::path::Path::new(&calendar.item).extension().is_some();
+    if !is_file {
+        return Err(
+            LabeledError::new(format!("Unknown holiday calendar: {}", calendar.item))
+                .with_label("not a built-in calendar or calendar file", calendar.span)
+                .with_help(format!(
+                    "use one of {} or a path to a .nuon or .toml file",
+                    BUILTIN_CALENDARS.join(", ")
+                )),
+        );
+    }
+
+    let Some(decl_id) = engine.find_decl("open")? else {
+        return Err(LabeledError::new(
+            "Could not find 'open' declaration".to_string(),
+        ));
+    };
+    let span = calendar.span;
+    let opened = engine.call_decl(
+        decl_id,
+        EvaluatedCall::new(span).with_positional(Value::string(calendar.item.clone(), span)),
+        PipelineData::empty(),
+        true,
+        false,
+    )?;
+    let value = opened.into_value(span)?;
+    let default_name = std::path::Path::new(&calendar.item)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    holiday_calendar_from_value(&value, &default_name, &local_time_zone(engine))
+}
+
+pub fn holiday_calendar_from_value(
+    value: &Value,
+    default_name: &str,
+    local_tz: &TimeZone,
+) -> Result<HolidayCalendar, LabeledError> {
+    let record = value.as_record().map_err(|_| {
+        LabeledError::new("A holiday calendar file must contain a record".to_string())
+            .with_label(format!("got {}", value.get_type()), value.span())
+    })?;
+
+    let mut calendar = match record.get("extends") {
+        Some(extends) => {
+            let extends = extends.as_str()?;
+            get_builtin_holiday_calendar(extends).ok_or_else(|| {
+                LabeledError::new(format!("Unknown holiday calendar to extend: {extends}"))
+                    .with_label("not a built-in calendar", value.span())
+            })?
+        }
+        None => HolidayCalendar {
+            name: String::new(),
+            weekend: vec![Weekday::Saturday, Weekday::Sunday],
+            rules: vec![],
+        },
+    };
+    calendar.name = match record.get("name") {
+        Some(name) => name.as_str()?.to_string(),
+        None => default_name.to_string(),
+    };
+    if let Some(weekend) = record.get("weekend") {
+        calendar.weekend = weekend
+            .as_list()?
+            .iter()
+            .map(|weekday| {
+                get_weekday_from_string(Spanned {
+                    item: weekday.as_str()?.to_string(),
+                    span: weekday.span(),
+                })
+            })
+            .collect::<Result<Vec<Weekday>, LabeledError>>()?;
+    }
+    if let Some(holidays) = record.get("holidays") {
+        for holiday in holidays.as_list()? {
+            calendar
+                .rules
+                .push(holiday_rule_from_value(holiday, local_tz)?);
+        }
+    }
+    Ok(calendar)
+}
+
+fn holiday_rule_from_value(
+    value: &Value,
+    local_tz: &TimeZone,
+) -> Result<HolidayRule, LabeledError> {
+    let span = value.span();
+    let record = value.as_record()?;
+    let int_field = |record: &Record, field: &str| -> Result<Option<i64>, LabeledError> {
+        record
+            .get(field)
+            .map(|value| value.as_int().map_err(LabeledError::from))
+            .transpose()
+    };
+    let small_field = |field: &str, min: i64, max: i64| -> Result<Option<i8>, LabeledError> {
+        match int_field(record, field)? {
+            Some(number) if (min..=max).contains(&number) => Ok(Some(number as i8)),
+            Some(number) => Err(LabeledError::new(format!(
+                "Holiday {field} must be between {min} and {max}"
+            ))
+            .with_label(format!("got {number}"), span)),
+            None => Ok(None),
+        }
+    };
+
+    let name = match record.get("name") {
+        Some(name) => name.as_str()?.to_string(),
+        None => {
+            return Err(LabeledError::new("Holidays need a name".to_string())
+                .with_label("missing name", span));
+        }
+    };
+    let month = small_field("month", 1, 12)?;
+    let day = small_field("day", 1, 31)?;
+    let nth = small_field("nth", -5, 5)?;
+    let weekday = record
+        .get("weekday")
+        .map(|weekday| {
+            get_weekday_from_string(Spanned {
+                item: weekday.as_str()?.to_string(),
+                span: weekday.span(),
+            })
+        })
+        .transpose()?;
+    let easter_offset = |field: &str| -> Result<Option<i16>, LabeledError> {
+        int_field(record, field)?
+            .map(|offset| {
+                i16::try_from(offset).map_err(|err| {
+                    LabeledError::new(err.to_string()).with_label(format!("invalid {field}"), span)
+                })
+            })
+            .transpose()
+    };
+    let easter = match (easter_offset("easter")?, easter_offset("orthodox_easter")?) {
+        (Some(offset), None) => Some(HolidayDate::Easter { offset }),
+        (None, Some(offset)) => Some(HolidayDate::OrthodoxEaster { offset }),
+        (None, None) => None,
+        (Some(_), Some(_)) => {
+            return Err(LabeledError::new(format!(
+                "Holiday {name} can't use both easter and orthodox_easter"
+            ))
+            .with_label("invalid holiday", span));
+        }
+    };
+
+    let date = match (record.get("date"), month, day, weekday, nth, easter) {
+        (Some(date), None, None, None, None, None) => {
+            HolidayDate::Once(get_zoned_from_value(date, local_tz)?.date())
+        }
+        (None, Some(month), Some(day), None, None, None) => HolidayDate::Fixed { month, day },
+        (None, Some(month), None, Some(weekday), Some(nth), None) if nth != 0 => {
+            HolidayDate::NthWeekday {
+                month,
+                weekday,
+                nth,
+            }
+        }
+        (None, None, None, None, None, Some(easter)) => easter,
+        _ => {
+            return Err(
+                LabeledError::new(format!("Holiday {name} has no valid date rule"))
+                    .with_label("invalid holiday", span)
+                    .with_help("use one of date, month and day, month, weekday and nth, easter or orthodox_easter"),
+            );
+        }
+    };
+    let observance = match record
+        .get("observed")
+        .map(|observed| observed.as_str().map_err(LabeledError::from))
+        .transpose()?
+    {
+        None | Some("none") => Observance::None,
+        Some("nearest") => Observance::Nearest,
+        Some("sunday-to-monday") => Observance::SundayToMonday,
+        Some("next-weekday") => Observance::NextWeekday,
+        Some(other) => {
+            return Err(
+                LabeledError::new(format!("Invalid observed option: {other}")).with_label(
+                    "expected one of none, nearest, sunday-to-monday or next-weekday",
+                    span,
+                ),
+            );
+        }
+    };
+    let year_field = |field: &str| -> Result<Option<i16>, LabeledError> {
+        int_field(record, field)?
+            .map(|year| {
+                i16::try_from(year).map_err(|err| {
+                    LabeledError::new(err.to_string()).with_label(format!("invalid {field}"), span)
+                })
+            })
+            .transpose()
+    };
+
+    Ok(HolidayRule {
+        name,
+        date,
+        observance,
+        since: year_field("since")?,
+        until: year_field("until")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    fn observed(calendar: &str, year: i16) -> Vec<(String, civil::Date)> {
+        get_builtin_holiday_calendar(calendar)
+            .unwrap()
+            .holidays_in_year(year)
+            .into_iter()
+            .map(|holiday| (holiday.name, holiday.observed))
+            .collect()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtHolidays)?;
+        plugin_test.test_command_examples(&DtIsHoliday)
+    }
+
+    #[test]
+    fn test_us_new_year_observed_in_previous_year() {
+        // 2022-01-01 was a Saturday
+        let holidays = observed("us", 2022);
+        assert_eq!(
+            holidays[0],
+            ("New Year's Day".to_string(), date(2021, 12, 31))
+        );
+        let nyse = observed("nyse", 2022);
+        assert_eq!(nyse[0], ("New Year's Day".to_string(), date(2022, 1, 1)));
+    }
+
+    #[test]
+    fn test_uk_christmas_substitute_days() {
+        // Christmas 2022 was a Sunday and Boxing Day a Monday
+        let holidays = observed("uk", 2022);
+        assert!(holidays.contains(&("Christmas Day".to_string(), date(2022, 12, 27))));
+        assert!(holidays.contains(&("Boxing Day".to_string(), date(2022, 12, 26))));
+        // Christmas 2021 was a Saturday and Boxing Day a Sunday
+        let holidays = observed("uk", 2021);
+        assert!(holidays.contains(&("Christmas Day".to_string(), date(2021, 12, 27))));
+        assert!(holidays.contains(&("Boxing Day".to_string(), date(2021, 12, 28))));
+    }
+
+    #[test]
+    fn test_calendar_from_value() {
+        let value = Value::test_record(record! {
+            "extends" => Value::test_string("target"),
+            "weekend" => Value::test_list(vec![Value::test_string("fri"), Value::test_string("sat")]),
+            "holidays" => Value::test_list(vec![
+                Value::test_record(record! {
+                    "name" => Value::test_string("Founders Day"),
+                    "month" => Value::test_int(3),
+                    "day" => Value::test_int(14),
+                    "observed" => Value::test_string("nearest"),
+                }),
+                Value::test_record(record! {
+                    "name" => Value::test_string("Summer Thursday"),
+                    "month" => Value::test_int(8),
+                    "weekday" => Value::test_string("thu"),
+                    "nth" => Value::test_int(-1),
+                }),
+            ]),
+        });
+        let calendar = holiday_calendar_from_value(&value, "acme", &TimeZone::system()).unwrap();
+        assert_eq!(calendar.name, "acme");
+        assert_eq!(calendar.rules.len(), 8);
+        let holidays = calendar.holidays_in_year(2025);
+        // 2025-03-14 is a Friday, which is on this calendar's weekend
+        assert!(
+            holidays
+                .iter()
+                .any(|holiday| holiday.name == "Founders Day"
+                    && holiday.observed == date(2025, 3, 13))
+        );
+        assert!(
+            holidays
+                .iter()
+                .any(|holiday| holiday.name == "Summer Thursday"
+                    && holiday.observed == date(2025, 8, 28))
+        );
+    }
+
+    #[test]
+    fn test_calendar_from_value_invalid_rule() {
+        let value = Value::test_record(record! {
+            "holidays" => Value::test_list(vec![Value::test_record(record! {
+                "name" => Value::test_string("Nowhere"),
+                "month" => Value::test_int(3),
+            })]),
+        });
+        assert!(holiday_calendar_from_value(&value, "bad", &TimeZone::system()).is_err());
+    }
+}
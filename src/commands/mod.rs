@@ -1,20 +1,62 @@
 // Command modules should be added here
 mod add;
+mod business_days;
+mod calendar_event;
+mod check;
+mod cron;
 mod diff;
 mod dt;
+mod easter;
+mod fiscal;
 mod format;
+mod guess_zone;
+mod holidays;
 mod now;
+mod overlap;
 mod part;
+mod period;
+mod round;
+mod rrule;
+mod schedule;
+mod seq;
+mod timezones;
 mod to;
+mod tzdb;
 mod utcnow;
 mod utils;
+mod weekday;
+mod work_time;
+mod world;
+mod yearfrac;
+mod zones_at;
 
 // Command structs should be exported here
 pub use add::DtAdd;
+pub use business_days::DtAddBusinessDays;
+pub use calendar_event::DtCalendarEvent;
+pub use check::DtCheck;
+pub use cron::{DtCronNext, DtCronPrev};
 pub use diff::DtDiff;
 pub use dt::Dt;
+pub use easter::DtEaster;
+pub use fiscal::DtFiscalPeriod;
 pub use format::DtFormat;
+pub use guess_zone::DtGuessZone;
+pub use holidays::{DtHolidays, DtIsHoliday};
 pub use now::DtNow;
+pub use overlap::DtOverlapHours;
 pub use part::DtPart;
+pub use period::{DtEndOf, DtStartOf};
+pub use round::{DtRound, DtTruncate};
+pub use rrule::DtRrule;
+pub use schedule::DtSchedule;
+pub use seq::DtSeq;
+pub use timezones::{DtListTimezones, DtToTimezone, DtTzTransitions};
 pub use to::DtTo;
+pub use tzdb::DtTzdb;
 pub use utcnow::DtUtcNow;
+pub use weekday::{DtNext, DtNthWeekday, DtPrev};
+pub use work_time::{DtAddWorkTime, DtWorkTime};
+pub use world::DtWorld;
+pub use yearfrac::DtYearFrac;
+pub use zones_at::DtZonesAt;
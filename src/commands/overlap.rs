@@ -0,0 +1,470 @@
+use super::business_days::{
+    BusinessCalendar, business_calendar_flags, get_business_calendar_from_call,
+};
+use super::tzdb::local_time_zone;
+use super::utils::{
+    get_time_zone_from_string, get_zoned_from_value, parse_jiff_span_from_duration_string,
+};
+use super::work_time::{WorkingHours, get_working_hours_from_string};
+use crate::DtPlugin;
+use jiff::{SignedDuration, Timestamp, ToSpan, civil, tz::TimeZone};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, IntoSpanned, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+pub struct DtOverlapHours;
+
+impl SimplePluginCommand for DtOverlapHours {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt overlap-hours"
+    }
+
+    fn signature(&self) -> Signature {
+        business_calendar_flags(
+            Signature::build(self.name())
+                .optional(
+                    "date",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date to plan on in the output time zone (defaults to today).",
+                )
+                .named(
+                    "hours",
+                    SyntaxShape::String,
+                    "Working hours for participants without their own, like 09:00-17:30 (default 09:00-17:00).",
+                    Some('r'),
+                )
+                .named(
+                    "core",
+                    SyntaxShape::String,
+                    "Core hours for participants without their own, like 10:00-16:00 (defaults to their working hours).",
+                    Some('c'),
+                )
+                .named(
+                    "timezone",
+                    SyntaxShape::String,
+                    "Time zone the date and the results are in (defaults to local).",
+                    Some('z'),
+                )
+                .switch(
+                    "rank",
+                    "Split the overlap into slots ranked by how many participants are in core hours",
+                    Some('k'),
+                )
+                .named(
+                    "slot",
+                    SyntaxShape::String,
+                    "Length of the ranked slots like T30m or T1h (default T30m).",
+                    Some('s'),
+                ),
+        )
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Find the times on a date when every piped in participant is within their working hours"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Participants are time zone names, or records with a zone and optionally a name, hours and core hours, e.g. { name: ana zone: Europe/Lisbon hours: '08:00-16:00' core: '10:00-15:00' }. Working hours only count on business days in each participant's own time zone, and DST changes on the date are taken into account. With --rank the overlap is split into slots sorted by how many participants are within core hours for the whole slot, listing who isn't."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "meeting",
+            "planner",
+            "overlap",
+            "working hours",
+            "time zone",
+            "team",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "[America/New_York Europe/London] | dt overlap-hours 2024-03-11 --timezone UTC",
+                description: "Find the shared working hours of New York and London in the week their DST changes don't line up",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "start" => Value::test_string("2024-03-11T13:00:00+00:00[UTC]"),
+                    "end" => Value::test_string("2024-03-11T17:00:00+00:00[UTC]"),
+                    "duration" => Value::test_duration(4 * 3_600_000_000_000),
+                })])),
+            },
+            Example {
+                example: "[{zone: America/New_York core: '10:00-16:00'} {name: ana zone: Europe/London core: '10:00-15:00'}] | dt overlap-hours 2024-03-11 --timezone UTC --rank --slot T1h",
+                description: "Rank one hour meeting slots by how many participants are within core hours",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "start" => Value::test_string("2024-03-11T14:00:00+00:00[UTC]"),
+                        "end" => Value::test_string("2024-03-11T15:00:00+00:00[UTC]"),
+                        "in_core" => Value::test_int(2),
+                        "outside_core" => Value::test_list(vec![]),
+                    }),
+                    Value::test_record(record! {
+                        "start" => Value::test_string("2024-03-11T13:00:00+00:00[UTC]"),
+                        "end" => Value::test_string("2024-03-11T14:00:00+00:00[UTC]"),
+                        "in_core" => Value::test_int(1),
+                        "outside_core" => Value::test_list(vec![Value::test_string("America/New_York")]),
+                    }),
+                    Value::test_record(record! {
+                        "start" => Value::test_string("2024-03-11T15:00:00+00:00[UTC]"),
+                        "end" => Value::test_string("2024-03-11T16:00:00+00:00[UTC]"),
+                        "in_core" => Value::test_int(1),
+                        "outside_core" => Value::test_list(vec![Value::test_string("ana")]),
+                    }),
+                    Value::test_record(record! {
+                        "start" => Value::test_string("2024-03-11T16:00:00+00:00[UTC]"),
+                        "end" => Value::test_string("2024-03-11T17:00:00+00:00[UTC]"),
+                        "in_core" => Value::test_int(1),
+                        "outside_core" => Value::test_list(vec![Value::test_string("ana")]),
+                    }),
+                ])),
+            },
+            Example {
+                example: "[Asia/Kolkata Europe/Berlin America/Chicago] | dt overlap-hours --hours 08:00-18:00",
+                description: "Find today's shared working hours with longer working days",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let date: Option<Value> = call.opt(0)?;
+        let hours = match call.get_flag("hours")? {
+            Some(hours) => get_working_hours_from_string(hours)?,
+            None => WorkingHours::default(),
+        };
+        let core = call
+            .get_flag("core")?
+            .map(get_working_hours_from_string)
+            .transpose()?;
+        let time_zone = match call.get_flag("timezone")? {
+            Some(time_zone) => get_time_zone_from_string(engine, time_zone)?,
+            None => local_tz.clone(),
+        };
+        let rank = call.has_flag("rank")?;
+        let slot = match call.get_flag::<Spanned<String>>("slot")? {
+            Some(slot) => get_slot_length_from_string(slot)?,
+            None => SignedDuration::from_mins(30),
+        };
+        let calendar = get_business_calendar_from_call(engine, call)?;
+
+        let participants = match input {
+            Value::List { vals, .. } if !vals.is_empty() => vals
+                .iter()
+                .map(|value| participant_from_value(engine, value, hours, core))
+                .collect::<Result<Vec<Participant>, LabeledError>>()?,
+            _ => {
+                return Err(LabeledError::new("Expected a list of participants")
+                    .with_label("pipe in time zones or participant records", input.span()));
+            }
+        };
+        let date = match date {
+            Some(date) => get_zoned_from_value(&date, &local_tz)?.date(),
+            None => Timestamp::now().to_zoned(time_zone.clone()).date(),
+        };
+        let day = day_bounds(date, &time_zone)?;
+
+        let mut overlap = vec![day];
+        for participant in &participants {
+            let windows = participant.windows(&participant.hours, &calendar, day)?;
+            overlap = intersect(&overlap, &windows);
+        }
+
+        let to_string = |timestamp: Timestamp| timestamp.to_zoned(time_zone.clone()).to_string();
+        if !rank {
+            return Ok(Value::list(
+                overlap
+                    .into_iter()
+                    .map(|(start, end)| {
+                        Value::record(
+                            record! {
+                                "start" => Value::string(to_string(start), call.head),
+                                "end" => Value::string(to_string(end), call.head),
+                                "duration" => Value::duration(
+                                    start.duration_until(end).as_nanos() as i64,
+                                    call.head,
+                                ),
+                            },
+                            call.head,
+                        )
+                    })
+                    .collect(),
+                call.head,
+            ));
+        }
+
+        let core_windows = participants
+            .iter()
+            .map(|participant| participant.windows(&participant.core, &calendar, day))
+            .collect::<Result<Vec<_>, LabeledError>>()?;
+        let rows = rank_slots(&overlap, slot, &core_windows)
+            .into_iter()
+            .map(|(start, end, outside)| {
+                Value::record(
+                    record! {
+                        "start" => Value::string(to_string(start), call.head),
+                        "end" => Value::string(to_string(end), call.head),
+                        "in_core" => Value::int((participants.len() - outside.len()) as i64, call.head),
+                        "outside_core" => Value::list(
+                            outside
+                                .into_iter()
+                                .map(|index| Value::string(&participants[index].name, call.head))
+                                .collect(),
+                            call.head,
+                        ),
+                    },
+                    call.head,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, call.head))
+    }
+}
+
+type Interval = (Timestamp, Timestamp);
+
+struct Participant {
+    name: String,
+    time_zone: TimeZone,
+    hours: WorkingHours,
+    core: WorkingHours,
+}
+
+impl Participant {
+    // The parts of a day that are within hours in the participant's time zone. A
+    // day in one zone can cover parts of two or three local dates in another.
+    fn windows(
+        &self,
+        hours: &WorkingHours,
+        calendar: &BusinessCalendar,
+        (start, end): Interval,
+    ) -> Result<Vec<Interval>, LabeledError> {
+        let first = start.to_zoned(self.time_zone.clone()).date();
+        let last = end.to_zoned(self.time_zone.clone()).date();
+        let mut windows = vec![];
+        for date in first.series(1.day()) {
+            if date > last {
+                break;
+            }
+            let Some((open, close)) = hours.window_on(calendar, date, &self.time_zone)? else {
+                continue;
+            };
+            let open = open.timestamp().max(start);
+            let close = close.timestamp().min(end);
+            if open < close {
+                windows.push((open, close));
+            }
+        }
+        Ok(windows)
+    }
+}
+
+fn participant_from_value(
+    engine: &EngineInterface,
+    value: &Value,
+    default_hours: WorkingHours,
+    default_core: Option<WorkingHours>,
+) -> Result<Participant, LabeledError> {
+    let span = value.span();
+    let spanned_string = |value: &Value| -> Result<Spanned<String>, LabeledError> {
+        Ok(value.as_str()?.to_string().into_spanned(value.span()))
+    };
+    let (zone, name, hours, core) = match value {
+        Value::String { .. } => (spanned_string(value)?, None, None, None),
+        Value::Record { val, .. } => {
+            let zone = val.get("zone").ok_or_else(|| {
+                LabeledError::new("A participant record needs a zone")
+                    .with_label("no zone column", span)
+            })?;
+            let optional = |column: &str| val.get(column).map(spanned_string).transpose();
+            (
+                spanned_string(zone)?,
+                optional("name")?,
+                optional("hours")?,
+                optional("core")?,
+            )
+        }
+        _ => {
+            return Err(
+                LabeledError::new("Expected a time zone or participant record")
+                    .with_label(format!("got {}", value.get_type()), span),
+            );
+        }
+    };
+    let hours = match hours {
+        Some(hours) => get_working_hours_from_string(hours)?,
+        None => default_hours,
+    };
+    let core = match core {
+        Some(core) => get_working_hours_from_string(core)?,
+        None => default_core.unwrap_or(hours),
+    };
+    Ok(Participant {
+        name: name.unwrap_or_else(|| zone.clone()).item,
+        time_zone: get_time_zone_from_string(engine, zone)?,
+        hours,
+        core,
+    })
+}
+
+fn get_slot_length_from_string(slot: Spanned<String>) -> Result<SignedDuration, LabeledError> {
+    let span = slot.span;
+    let length = parse_jiff_span_from_duration_string(slot)
+        .and_then(|length| {
+            SignedDuration::try_from(length).map_err(|err| LabeledError::new(err.to_string()))
+        })
+        .map_err(|err| err.with_label("expected a duration like T30m or T1h", span))?;
+    if !length.is_positive() {
+        return Err(LabeledError::new("Slot length must be positive")
+            .with_label("expected a duration like T30m or T1h", span));
+    }
+    Ok(length)
+}
+
+// The instants a date starts and ends in a time zone, which aren't 24 hours apart
+// on DST change dates
+fn day_bounds(date: civil::Date, time_zone: &TimeZone) -> Result<Interval, LabeledError> {
+    let start_of = |date: civil::Date| {
+        date.to_zoned(time_zone.clone())
+            .and_then(|datetime| datetime.start_of_day())
+            .map(|datetime| datetime.timestamp())
+            .map_err(|err| LabeledError::new(err.to_string()))
+    };
+    let tomorrow = date
+        .tomorrow()
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+    Ok((start_of(date)?, start_of(tomorrow)?))
+}
+
+// Intersect two sorted lists of non-overlapping intervals
+fn intersect(first: &[Interval], second: &[Interval]) -> Vec<Interval> {
+    let (mut i, mut j) = (0, 0);
+    let mut intersection = vec![];
+    while i < first.len() && j < second.len() {
+        let start = first[i].0.max(second[j].0);
+        let end = first[i].1.min(second[j].1);
+        if start < end {
+            intersection.push((start, end));
+        }
+        if first[i].1 < second[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    intersection
+}
+
+// Split the overlap into back to back slots, listing the participants who are
+// outside core hours for each. Slots are sorted with the fewest outside first,
+// then by start.
+fn rank_slots(
+    overlap: &[Interval],
+    slot: SignedDuration,
+    core_windows: &[Vec<Interval>],
+) -> Vec<(Timestamp, Timestamp, Vec<usize>)> {
+    let mut slots = vec![];
+    for &(start, end) in overlap {
+        let mut slot_start = start;
+        while let Ok(slot_end) = slot_start.checked_add(slot) {
+            if slot_end > end {
+                break;
+            }
+            let outside = core_windows
+                .iter()
+                .enumerate()
+                .filter(|(_, windows)| {
+                    !windows
+                        .iter()
+                        .any(|&(open, close)| open <= slot_start && slot_end <= close)
+                })
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>();
+            slots.push((slot_start, slot_end, outside));
+            slot_start = slot_end;
+        }
+    }
+    slots.sort_by_key(|(start, _, outside)| (outside.len(), *start));
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::Zoned;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    fn timestamp(datetime: &str) -> Timestamp {
+        datetime.parse::<Zoned>().unwrap().timestamp()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtOverlapHours)
+    }
+
+    #[test]
+    fn test_day_bounds_on_dst_change() {
+        let chicago = TimeZone::get("America/Chicago").unwrap();
+        let (start, end) = day_bounds(date(2024, 3, 10), &chicago).unwrap();
+        assert_eq!(start.duration_until(end), SignedDuration::from_hours(23));
+    }
+
+    #[test]
+    fn test_windows_across_local_dates() {
+        // A Tokyo day covers the end of one Los Angeles working day and the start of
+        // the next
+        let participant = Participant {
+            name: "la".to_string(),
+            time_zone: TimeZone::get("America/Los_Angeles").unwrap(),
+            hours: get_working_hours_from_string(
+                "06:00-18:00"
+                    .to_string()
+                    .into_spanned(nu_protocol::Span::unknown()),
+            )
+            .unwrap(),
+            core: WorkingHours::default(),
+        };
+        let day = day_bounds(date(2024, 8, 22), &TimeZone::get("Asia/Tokyo").unwrap()).unwrap();
+        let windows = participant
+            .windows(&participant.hours, &BusinessCalendar::default(), day)
+            .unwrap();
+        assert_eq!(
+            windows,
+            vec![
+                (
+                    timestamp("2024-08-21T15:00:00+00:00[UTC]"),
+                    timestamp("2024-08-22T01:00:00+00:00[UTC]")
+                ),
+                (
+                    timestamp("2024-08-22T13:00:00+00:00[UTC]"),
+                    timestamp("2024-08-22T15:00:00+00:00[UTC]")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_intersect() {
+        let at = |hour: i64| Timestamp::UNIX_EPOCH + SignedDuration::from_hours(hour);
+        let first = vec![(at(1), at(5)), (at(7), at(10))];
+        let second = vec![(at(3), at(8)), (at(9), at(12))];
+        assert_eq!(
+            intersect(&first, &second),
+            vec![(at(3), at(5)), (at(7), at(8)), (at(9), at(10))]
+        );
+        assert!(intersect(&first, &[]).is_empty());
+    }
+}
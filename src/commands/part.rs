@@ -1,3 +1,5 @@
+use super::fiscal::{fiscal_calendar_flags, get_fiscal_calendar_from_call};
+use super::tzdb::local_time_zone;
 use super::utils::{
     get_part_from_zoned_as_i16, get_unit_abbreviations, parse_datetime_string_add_nanos_optionally,
 };
@@ -15,14 +17,16 @@ impl SimplePluginCommand for DtPart {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(self.name())
-            .rest(
-                "unit",
-                SyntaxShape::String,
-                "Unit name to extract from a date/datetime.",
-            )
-            .switch("list", "List the unit name abbreviations", Some('l'))
-            .category(Category::Date)
+        fiscal_calendar_flags(
+            Signature::build(self.name())
+                .rest(
+                    "unit",
+                    SyntaxShape::String,
+                    "Unit name to extract from a date/datetime.",
+                )
+                .switch("list", "List the unit name abbreviations", Some('l')),
+        )
+        .category(Category::Date)
     }
 
     fn description(&self) -> &str {
@@ -46,6 +50,16 @@ impl SimplePluginCommand for DtPart {
                 description: "Return the month part of the provided nushell datetime",
                 result: Some(Value::test_int(8)),
             },
+            Example {
+                example: "'2024-08-25' | dt part fiscal_quarter --fiscal-start 10",
+                description: "Return the fiscal quarter of a fiscal year starting in October",
+                result: Some(Value::test_int(4)),
+            },
+            Example {
+                example: "'2024-08-25' | dt part fiscal_week --fiscal-start 2 --fiscal-pattern 4-4-5 --fiscal-nearest",
+                description: "Return the fiscal week of a 4-4-5 retail calendar ending on the Saturday nearest the end of January",
+                result: Some(Value::test_int(30)),
+            },
             Example {
                 example: "(date now) | dt part mon",
                 description: "Return the month part of the provided nushell datetime from the date command",
@@ -66,10 +80,11 @@ impl SimplePluginCommand for DtPart {
     fn run(
         &self,
         _plugin: &DtPlugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
         let span = input.span();
         let list = call.has_flag("list")?;
         if list {
@@ -108,16 +123,20 @@ impl SimplePluginCommand for DtPart {
                             None,
                             span,
                             None,
+                            &local_tz,
                         )?
                     }
                     Value::String { val, .. } => {
                         // eprintln!("Zoned: {:?}", zdt);
-                        parse_datetime_string_add_nanos_optionally(val, None, span, None)?
+                        parse_datetime_string_add_nanos_optionally(
+                            val, None, span, None, &local_tz,
+                        )?
                     }
                     _ => return Err(LabeledError::new("Expected a date or datetime".to_string())),
                 };
 
-                let date_part = get_part_from_zoned_as_i16(unit[0].clone(), datetime)?;
+                let fiscal = get_fiscal_calendar_from_call(call)?;
+                let date_part = get_part_from_zoned_as_i16(unit[0].clone(), datetime, &fiscal)?;
 
                 Ok(Value::int(date_part.into(), call.head))
             }
@@ -0,0 +1,344 @@
+use super::fiscal::{
+    FiscalCalendar, FiscalUnit, fiscal_calendar_flags, get_fiscal_calendar_from_call,
+};
+use super::tzdb::local_time_zone;
+use super::utils::{
+    get_unit_from_unit_string, get_weekday_from_string, get_zoned_from_value,
+    is_quarter_unit_string,
+};
+use crate::DtPlugin;
+use jiff::{RoundMode, ToSpan, Unit, Zoned, ZonedRound, civil::Weekday};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+
+pub struct DtStartOf;
+
+impl SimplePluginCommand for DtStartOf {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt start-of"
+    }
+
+    fn signature(&self) -> Signature {
+        period_signature(self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Return the first instant of the period containing the provided date or datetime"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "period", "boundary", "beginning", "first"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt start-of month",
+                description: "Return the first instant of the month",
+                result: Some(Value::test_string(
+                    "2024-08-01T00:00:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt start-of week --week-start sunday",
+                description: "Return the first instant of the week when weeks start on Sunday",
+                result: Some(Value::test_string(
+                    "2024-08-25T00:00:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt start-of year --fiscal-start 10",
+                description: "Return the first instant of a fiscal year starting in October",
+                result: Some(Value::test_string(
+                    "2023-10-01T00:00:00-05:00[America/Chicago]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let (start, _) = get_period_bounds_from_call(engine, call, input)?;
+        Ok(Value::string(start.to_string(), call.head))
+    }
+}
+
+pub struct DtEndOf;
+
+impl SimplePluginCommand for DtEndOf {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt end-of"
+    }
+
+    fn signature(&self) -> Signature {
+        period_signature(self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Return the last nanosecond of the period containing the provided date or datetime"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "period", "boundary", "ending", "last"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt end-of month",
+                description: "Return the last nanosecond of the month",
+                result: Some(Value::test_string(
+                    "2024-08-31T23:59:59.999999999-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-11-25T10:52:30-06:00[America/Chicago]' | dt end-of qtr",
+                description: "Return the last nanosecond of the quarter",
+                result: Some(Value::test_string(
+                    "2024-12-31T23:59:59.999999999-06:00[America/Chicago]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let (_, next_start) = get_period_bounds_from_call(engine, call, input)?;
+        let end = next_start
+            .checked_sub(1.nanosecond())
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        Ok(Value::string(end.to_string(), call.head))
+    }
+}
+
+// The fiscal calendar flags apply to year and quarter
+fn period_signature(name: &str) -> Signature {
+    fiscal_calendar_flags(
+        Signature::build(name)
+            .required(
+                "unit",
+                SyntaxShape::String,
+                "Period unit: year, quarter, month, week, day, hour, minute or second.",
+            )
+            .named(
+                "week-start",
+                SyntaxShape::String,
+                "Day weeks start on, e.g. monday (default), sunday or saturday.",
+                Some('w'),
+            ),
+    )
+    .category(Category::Date)
+}
+
+fn get_period_bounds_from_call(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+    input: &Value,
+) -> Result<(Zoned, Zoned), LabeledError> {
+    let unit: Spanned<String> = call.req(0)?;
+    let week_start = match call.get_flag("week-start")? {
+        Some(weekday) => get_weekday_from_string(weekday)?,
+        None => Weekday::Monday,
+    };
+    let fiscal = get_fiscal_calendar_from_call(call)?;
+
+    let datetime = get_zoned_from_value(input, &local_time_zone(engine))?;
+    get_period_bounds(&datetime, unit, week_start, &fiscal)
+}
+
+// Return the first instant of the period containing the datetime and the first
+// instant of the following period, both in the datetime's own time zone. Periods of
+// a day or longer start at the first instant of their first day, which isn't always
+// midnight when a DST transition happens at midnight.
+pub fn get_period_bounds(
+    datetime: &Zoned,
+    unit: Spanned<String>,
+    week_start: Weekday,
+    fiscal: &FiscalCalendar,
+) -> Result<(Zoned, Zoned), LabeledError> {
+    let unit_kind = if is_quarter_unit_string(&unit.item) {
+        None
+    } else {
+        Some(get_unit_from_unit_string(unit.item.clone())?)
+    };
+
+    let date = datetime.date();
+    let (start, end) = match unit_kind {
+        Some(Unit::Year) => fiscal.period_bounds(&fiscal.locate(date)?, FiscalUnit::Year)?,
+        None => fiscal.period_bounds(&fiscal.locate(date)?, FiscalUnit::Quarter)?,
+        Some(Unit::Month) => {
+            let start = date.first_of_month();
+            let end = start
+                .checked_add(1.month())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            (start, end)
+        }
+        Some(Unit::Week) => {
+            let days_into_week = (date.weekday().to_monday_zero_offset()
+                - week_start.to_monday_zero_offset())
+            .rem_euclid(7);
+            let start = date
+                .checked_sub(i64::from(days_into_week).days())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            let end = start
+                .checked_add(1.week())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            (start, end)
+        }
+        Some(Unit::Day) => {
+            let end = date
+                .tomorrow()
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            (date, end)
+        }
+        Some(time_unit) => {
+            let start = datetime
+                .round(ZonedRound::new().smallest(time_unit).mode(RoundMode::Trunc))
+                .map_err(|err| {
+                    LabeledError::new(format!("Error finding start of period: {err}"))
+                        .with_label(format!("unit was {}", unit.item), unit.span)
+                })?;
+            let length = match time_unit {
+                Unit::Hour => 1.hour(),
+                Unit::Minute => 1.minute(),
+                Unit::Second => 1.second(),
+                Unit::Millisecond => 1.millisecond(),
+                Unit::Microsecond => 1.microsecond(),
+                _ => 1.nanosecond(),
+            };
+            let end = start
+                .checked_add(length)
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            return Ok((start, end));
+        }
+    };
+
+    let time_zone = datetime.time_zone().clone();
+    let start = start
+        .to_zoned(time_zone.clone())
+        .and_then(|zdt| zdt.start_of_day())
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+    let end = end
+        .to_zoned(time_zone)
+        .and_then(|zdt| zdt.start_of_day())
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+    use nu_protocol::Span;
+
+    fn bounds(datetime: &str, unit: &str, week_start: Weekday, fiscal: i8) -> (String, String) {
+        let datetime: Zoned = datetime.parse().unwrap();
+        let fiscal = FiscalCalendar {
+            start_month: fiscal,
+            ..FiscalCalendar::default()
+        };
+        let (start, end) = get_period_bounds(
+            &datetime,
+            Spanned {
+                item: unit.to_string(),
+                span: Span::unknown(),
+            },
+            week_start,
+            &fiscal,
+        )
+        .unwrap();
+        (start.to_string(), end.to_string())
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtStartOf)?;
+        plugin_test.test_command_examples(&DtEndOf)
+    }
+
+    #[test]
+    fn test_start_of_day_when_midnight_does_not_exist() {
+        // Sao Paulo skipped from midnight to 01:00 on 2018-11-04
+        let (start, end) = bounds(
+            "2018-11-04T12:00:00-02:00[America/Sao_Paulo]",
+            "day",
+            Weekday::Monday,
+            1,
+        );
+        assert_eq!(start, "2018-11-04T01:00:00-02:00[America/Sao_Paulo]");
+        assert_eq!(end, "2018-11-05T00:00:00-02:00[America/Sao_Paulo]");
+    }
+
+    #[test]
+    fn test_week_start_saturday() {
+        let (start, end) = bounds(
+            "2024-08-23T10:00:00+00:00[UTC]",
+            "week",
+            Weekday::Saturday,
+            1,
+        );
+        assert_eq!(start, "2024-08-17T00:00:00+00:00[UTC]");
+        assert_eq!(end, "2024-08-24T00:00:00+00:00[UTC]");
+    }
+
+    #[test]
+    fn test_fiscal_quarter() {
+        let (start, end) = bounds(
+            "2024-03-15T10:00:00+00:00[UTC]",
+            "quarter",
+            Weekday::Monday,
+            2,
+        );
+        assert_eq!(start, "2024-02-01T00:00:00+00:00[UTC]");
+        assert_eq!(end, "2024-05-01T00:00:00+00:00[UTC]");
+    }
+
+    #[test]
+    fn test_week_based_fiscal_quarter() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        let fiscal_flags = "--fiscal-start 2 --fiscal-pattern 4-4-5 --fiscal-nearest";
+        let start = plugin_test
+            .eval(&format!(
+                "'2024-02-03T12:00:00+00:00[UTC]' | dt start-of quarter {fiscal_flags}"
+            ))?
+            .into_value(Span::test_data())?;
+        let end = plugin_test
+            .eval(&format!(
+                "'2024-02-03T12:00:00+00:00[UTC]' | dt end-of qtr {fiscal_flags}"
+            ))?
+            .into_value(Span::test_data())?;
+        assert_eq!(start, Value::test_string("2023-10-29T00:00:00+00:00[UTC]"));
+        assert_eq!(
+            end,
+            Value::test_string("2024-02-03T23:59:59.999999999+00:00[UTC]")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_hour_bounds() {
+        let (start, end) = bounds(
+            "2024-11-03T01:30:00-05:00[America/Chicago]",
+            "hour",
+            Weekday::Monday,
+            1,
+        );
+        assert_eq!(start, "2024-11-03T01:00:00-05:00[America/Chicago]");
+        assert_eq!(end, "2024-11-03T01:00:00-06:00[America/Chicago]");
+    }
+}
@@ -0,0 +1,388 @@
+use super::tzdb::local_time_zone;
+use super::utils::{
+    get_round_mode_from_string, get_unit_from_unit_string, get_zoned_from_value,
+    is_quarter_unit_string,
+};
+use crate::DtPlugin;
+use jiff::{RoundMode, ToSpan, Unit, Zoned, ZonedRound, civil};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+
+pub struct DtRound;
+
+impl SimplePluginCommand for DtRound {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt round"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "unit",
+                SyntaxShape::String,
+                "Unit to round the date/datetime to.",
+            )
+            .named(
+                "increment",
+                SyntaxShape::Int,
+                "Round to a multiple of this many units (default 1).",
+                Some('i'),
+            )
+            .named(
+                "mode",
+                SyntaxShape::String,
+                "Rounding mode: ceil, floor, expand, trunc, half-ceil, half-floor, half-expand (default), half-trunc or half-even.",
+                Some('m'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Round the provided date or datetime to a unit in its own time zone"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "round", "bucket", "nearest"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt round min --increment 15",
+                description: "Round a datetime to the nearest 15 minutes",
+                result: Some(Value::test_string(
+                    "2024-08-25T11:00:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt round hr --mode floor",
+                description: "Round a datetime down to the hour",
+                result: Some(Value::test_string(
+                    "2024-08-25T10:00:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt round qtr",
+                description: "Round a datetime to the nearest quarter boundary",
+                result: Some(Value::test_string(
+                    "2024-10-01T00:00:00-05:00[America/Chicago]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let unit: Spanned<String> = call.req(0)?;
+        let increment: Option<i64> = call.get_flag("increment")?;
+        let mode = match call.get_flag("mode")? {
+            Some(mode) => get_round_mode_from_string(mode)?,
+            None => RoundMode::HalfExpand,
+        };
+
+        let datetime = get_zoned_from_value(input, &local_tz)?;
+        let rounded = round_zoned(&datetime, unit, increment.unwrap_or(1), mode)?;
+        Ok(Value::string(rounded.to_string(), call.head))
+    }
+}
+
+pub struct DtTruncate;
+
+impl SimplePluginCommand for DtTruncate {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt truncate"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "unit",
+                SyntaxShape::String,
+                "Unit to truncate the date/datetime to.",
+            )
+            .named(
+                "increment",
+                SyntaxShape::Int,
+                "Truncate to a multiple of this many units (default 1).",
+                Some('i'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Truncate the provided date or datetime to a unit in its own time zone"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "round", "bucket", "floor"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt truncate min --increment 5",
+                description: "Truncate a datetime to a 5 minute mark",
+                result: Some(Value::test_string(
+                    "2024-08-25T10:50:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt truncate month",
+                description: "Truncate a datetime to the start of its month",
+                result: Some(Value::test_string(
+                    "2024-08-01T00:00:00-05:00[America/Chicago]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let unit: Spanned<String> = call.req(0)?;
+        let increment: Option<i64> = call.get_flag("increment")?;
+
+        let datetime = get_zoned_from_value(input, &local_tz)?;
+        let truncated = round_zoned(&datetime, unit, increment.unwrap_or(1), RoundMode::Trunc)?;
+        Ok(Value::string(truncated.to_string(), call.head))
+    }
+}
+
+// Round a zoned datetime to a unit. Units up to days are handled by jiff's ZonedRound,
+// weeks, months, quarters and years are rounded between calendar boundaries in the
+// datetime's own time zone.
+pub fn round_zoned(
+    datetime: &Zoned,
+    unit: Spanned<String>,
+    increment: i64,
+    mode: RoundMode,
+) -> Result<Zoned, LabeledError> {
+    if increment < 1 {
+        return Err(LabeledError::new("increment must be greater than zero")
+            .with_label(format!("increment was {increment}"), unit.span));
+    }
+
+    let (unit_kind, increment) = if is_quarter_unit_string(&unit.item) {
+        (Unit::Month, increment * 3)
+    } else {
+        (get_unit_from_unit_string(unit.item.clone())?, increment)
+    };
+
+    let date = datetime.date();
+    // Find the civil boundary at the start of the bucket the datetime is in and the
+    // index of that bucket so half-even can pick the even one
+    let (lower, bucket_index) = match unit_kind {
+        Unit::Year => {
+            let year = i64::from(date.year()).div_euclid(increment) * increment;
+            let lower = civil::Date::new(year as i16, 1, 1)
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            (lower, year / increment)
+        }
+        Unit::Month => {
+            if 12 % increment != 0 {
+                return Err(
+                    LabeledError::new("month increments must divide evenly into a year")
+                        .with_label(format!("unit was {}", unit.item), unit.span),
+                );
+            }
+            let months = (i64::from(date.year()) * 12 + i64::from(date.month()) - 1)
+                .div_euclid(increment)
+                * increment;
+            let lower = civil::Date::new(
+                months.div_euclid(12) as i16,
+                (months.rem_euclid(12) + 1) as i8,
+                1,
+            )
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+            (lower, months / increment)
+        }
+        Unit::Week => {
+            if increment != 1 {
+                return Err(LabeledError::new("week increments must be 1")
+                    .with_label(format!("unit was {}", unit.item), unit.span));
+            }
+            let lower = date
+                .checked_sub(i64::from(date.weekday().to_monday_zero_offset()).days())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            let epoch_days = lower
+                .since(civil::date(1970, 1, 5))
+                .map_err(|err| LabeledError::new(err.to_string()))?
+                .get_days();
+            (lower, i64::from(epoch_days) / 7)
+        }
+        _ => {
+            return datetime
+                .round(
+                    ZonedRound::new()
+                        .smallest(unit_kind)
+                        .increment(increment)
+                        .mode(mode),
+                )
+                .map_err(|err| {
+                    LabeledError::new(format!("Error rounding datetime: {err}"))
+                        .with_label(format!("unit was {}", unit.item), unit.span)
+                });
+        }
+    };
+
+    let upper = match unit_kind {
+        Unit::Year => lower.checked_add(increment.years()),
+        Unit::Month => lower.checked_add(increment.months()),
+        _ => lower.checked_add(1.week()),
+    }
+    .map_err(|err| LabeledError::new(err.to_string()))?;
+
+    let time_zone = datetime.time_zone().clone();
+    let lower = lower
+        .to_zoned(time_zone.clone())
+        .and_then(|zdt| zdt.start_of_day())
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+    let upper = upper
+        .to_zoned(time_zone)
+        .and_then(|zdt| zdt.start_of_day())
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+
+    let position = datetime.timestamp().as_nanosecond() - lower.timestamp().as_nanosecond();
+    if position == 0 {
+        return Ok(lower);
+    }
+    let length = upper.timestamp().as_nanosecond() - lower.timestamp().as_nanosecond();
+
+    // Datetimes are rounded like positive numbers, so trunc is floor and expand is ceil
+    let round_up = match mode {
+        RoundMode::Floor | RoundMode::Trunc => false,
+        RoundMode::Ceil | RoundMode::Expand => true,
+        _ => match (position * 2).cmp(&length) {
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => match mode {
+                RoundMode::HalfEven => bucket_index % 2 != 0,
+                RoundMode::HalfFloor | RoundMode::HalfTrunc => false,
+                _ => true,
+            },
+        },
+    };
+
+    Ok(if round_up { upper } else { lower })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+    use nu_protocol::Span;
+
+    fn round(datetime: &str, unit: &str, increment: i64, mode: RoundMode) -> String {
+        let datetime: Zoned = datetime.parse().unwrap();
+        round_zoned(
+            &datetime,
+            Spanned {
+                item: unit.to_string(),
+                span: Span::unknown(),
+            },
+            increment,
+            mode,
+        )
+        .unwrap()
+        .to_string()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtRound)?;
+        plugin_test.test_command_examples(&DtTruncate)
+    }
+
+    #[test]
+    fn test_round_week_starts_on_monday() {
+        assert_eq!(
+            round(
+                "2024-08-25T10:00:00-05:00[America/Chicago]",
+                "week",
+                1,
+                RoundMode::Floor
+            ),
+            "2024-08-19T00:00:00-05:00[America/Chicago]"
+        );
+    }
+
+    #[test]
+    fn test_round_day_across_dst_gap() {
+        // 2024-03-10 is only 23 hours long in Chicago
+        assert_eq!(
+            round(
+                "2024-03-10T12:45:00-05:00[America/Chicago]",
+                "day",
+                1,
+                RoundMode::HalfExpand
+            ),
+            "2024-03-11T00:00:00-05:00[America/Chicago]"
+        );
+    }
+
+    #[test]
+    fn test_round_year_half_even() {
+        assert_eq!(
+            round(
+                "2023-07-02T12:00:00+00:00[UTC]",
+                "year",
+                1,
+                RoundMode::HalfEven
+            ),
+            "2024-01-01T00:00:00+00:00[UTC]"
+        );
+        assert_eq!(
+            round(
+                "2022-11-01T00:00:00+00:00[UTC]",
+                "year",
+                2,
+                RoundMode::HalfExpand
+            ),
+            "2022-01-01T00:00:00+00:00[UTC]"
+        );
+    }
+
+    #[test]
+    fn test_truncate_month_before_year_zero() {
+        assert_eq!(
+            round(
+                "-000001-05-15T00:00:00+00:00[UTC]",
+                "month",
+                3,
+                RoundMode::Trunc
+            ),
+            "-000001-04-01T00:00:00+00:00[UTC]"
+        );
+    }
+
+    #[test]
+    fn test_round_invalid_month_increment() {
+        let datetime: Zoned = "2024-08-25T10:00:00+00:00[UTC]".parse().unwrap();
+        let result = round_zoned(
+            &datetime,
+            Spanned {
+                item: "month".to_string(),
+                span: Span::unknown(),
+            },
+            5,
+            RoundMode::Trunc,
+        );
+        assert!(result.is_err());
+    }
+}
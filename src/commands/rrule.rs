@@ -0,0 +1,1078 @@
+use super::tzdb::{local_time_zone, time_zone_database};
+use super::utils::get_zoned_from_value;
+use crate::DtPlugin;
+use jiff::{
+    Timestamp, ToSpan, Zoned, civil,
+    civil::Weekday,
+    tz::{Offset, OffsetConflict, TimeZone},
+};
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, LabeledError, PipelineData, Signals,
+    Signature, Span as NuSpan, Spanned, SyntaxShape, Type, Value,
+};
+use std::collections::VecDeque;
+
+pub struct DtRrule;
+
+impl PluginCommand for DtRrule {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt rrule"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(PluginCommand::name(self))
+            .input_output_types(vec![
+                (Type::String, Type::List(Type::String.into())),
+                (Type::Date, Type::List(Type::String.into())),
+                (Type::Nothing, Type::List(Type::String.into())),
+            ])
+            .required(
+                "rule",
+                SyntaxShape::String,
+                "RFC 5545 RRULE, optionally with DTSTART, EXDATE and RDATE lines.",
+            )
+            .named(
+                "exdate",
+                SyntaxShape::List(Box::new(SyntaxShape::OneOf(vec![
+                    SyntaxShape::String,
+                    SyntaxShape::DateTime,
+                ]))),
+                "Datetimes to exclude from the recurrence set.",
+                Some('x'),
+            )
+            .named(
+                "rdate",
+                SyntaxShape::List(Box::new(SyntaxShape::OneOf(vec![
+                    SyntaxShape::String,
+                    SyntaxShape::DateTime,
+                ]))),
+                "Extra datetimes to include in the recurrence set.",
+                Some('r'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Stream the occurrences of an RFC 5545 recurrence rule starting at the piped in DTSTART"
+    }
+
+    fn extra_description(&self) -> &str {
+        "Supports FREQ, INTERVAL, COUNT, UNTIL, BYMONTH, BYWEEKNO, BYYEARDAY, BYMONTHDAY, BYDAY, BYHOUR, BYMINUTE, BYSECOND, BYSETPOS and WKST. Occurrences are computed in the time zone of DTSTART, so local times stay fixed across DST changes. Rules without COUNT or UNTIL never end, so pipe them into `first` or `take`."
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "date",
+            "time",
+            "recurrence",
+            "recurring",
+            "ical",
+            "icalendar",
+            "rfc5545",
+            "schedule",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-01-26T10:00:00-05:00[America/New_York]' | dt rrule 'FREQ=MONTHLY;BYDAY=-1FR;COUNT=3'",
+                description: "Return the last Friday of the next three months",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-01-26T10:00:00-05:00[America/New_York]"),
+                    Value::test_string("2024-02-23T10:00:00-05:00[America/New_York]"),
+                    Value::test_string("2024-03-29T10:00:00-04:00[America/New_York]"),
+                ])),
+            },
+            Example {
+                example: "'2024-01-31T09:00:00-05:00[America/New_York]' | dt rrule 'FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;COUNT=3'",
+                description: "Return the last working day of each month",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-01-31T09:00:00-05:00[America/New_York]"),
+                    Value::test_string("2024-02-29T09:00:00-05:00[America/New_York]"),
+                    Value::test_string("2024-03-29T09:00:00-04:00[America/New_York]"),
+                ])),
+            },
+            Example {
+                example: "dt rrule \"DTSTART;TZID=Europe/Berlin:20240325T080000\nRRULE:FREQ=DAILY;UNTIL=20240328T235959Z\nEXDATE;TZID=Europe/Berlin:20240327T080000\"",
+                description: "Expand an iCalendar recurrence with DTSTART and EXDATE lines",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-03-25T08:00:00+01:00[Europe/Berlin]"),
+                    Value::test_string("2024-03-26T08:00:00+01:00[Europe/Berlin]"),
+                    Value::test_string("2024-03-28T08:00:00+01:00[Europe/Berlin]"),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let rule_text: Spanned<String> = call.req(0)?;
+        let exdate_values: Option<Vec<Value>> = call.get_flag("exdate")?;
+        let rdate_values: Option<Vec<Value>> = call.get_flag("rdate")?;
+
+        let input = input.into_value(call.head)?;
+        let dtstart = match input {
+            Value::Nothing { .. } => None,
+            input => Some(get_zoned_from_value(&input, &local_tz)?),
+        };
+
+        let mut recurrence = parse_recurrence_text(&rule_text, dtstart, &local_tz)?;
+        for value in exdate_values.unwrap_or_default() {
+            recurrence
+                .exdates
+                .push(get_zoned_from_value(&value, &local_tz)?.timestamp());
+        }
+        for value in rdate_values.unwrap_or_default() {
+            recurrence
+                .rdates
+                .push(get_zoned_from_value(&value, &local_tz)?);
+        }
+
+        let span = call.head;
+        let occurrences =
+            recurrence_set(recurrence, engine.signals().clone()).map(move |occurrence| {
+                match occurrence {
+                    Ok(occurrence) => Value::string(occurrence.to_string(), span),
+                    Err(err) => Value::error(err.into(), span),
+                }
+            });
+        Ok(occurrences.into_pipeline_data(span, engine.signals().clone()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Clone, Debug)]
+pub enum Until {
+    Instant(Timestamp),
+    // A floating or date only UNTIL is compared against local civil datetimes
+    Civil(civil::DateTime),
+}
+
+#[derive(Clone, Debug)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: i64,
+    pub count: Option<i64>,
+    pub until: Option<Until>,
+    pub by_month: Vec<i8>,
+    pub by_week_no: Vec<i8>,
+    pub by_year_day: Vec<i16>,
+    pub by_month_day: Vec<i8>,
+    pub by_day: Vec<(Option<i8>, Weekday)>,
+    pub by_hour: Vec<i8>,
+    pub by_minute: Vec<i8>,
+    pub by_second: Vec<i8>,
+    pub by_set_pos: Vec<i32>,
+    pub week_start: Weekday,
+}
+
+pub struct Recurrence {
+    pub dtstart: Zoned,
+    pub rule: Option<RecurrenceRule>,
+    pub exdates: Vec<Timestamp>,
+    pub rdates: Vec<Zoned>,
+}
+
+// Parse either a bare RRULE (with or without the RRULE: prefix) or iCalendar style
+// content lines with DTSTART, RRULE, EXDATE and RDATE properties
+pub fn parse_recurrence_text(
+    text: &Spanned<String>,
+    dtstart: Option<Zoned>,
+    local_tz: &TimeZone,
+) -> Result<Recurrence, LabeledError> {
+    let mut dtstart = dtstart;
+    let mut rule = None;
+    let mut exdate_lines = vec![];
+    let mut rdate_lines = vec![];
+
+    for line in text
+        .item
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        let property = line
+            .split([';', ':'])
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        let (name_and_params, value) = match line.split_once(':') {
+            Some((name, value))
+                if matches!(property.as_ref(), "DTSTART" | "RRULE" | "EXDATE" | "RDATE") =>
+            {
+                (name, value)
+            }
+            _ => ("RRULE", line),
+        };
+        let mut params = name_and_params.split(';');
+        let name = params.next().unwrap_or_default().to_uppercase();
+        let tzid = params.find_map(|param| {
+            param
+                .split_once('=')
+                .filter(|(key, _)| key.eq_ignore_ascii_case("TZID"))
+                .map(|(_, value)| value.to_string())
+        });
+        match name.as_ref() {
+            "DTSTART" => {
+                if dtstart.is_none() {
+                    dtstart = Some(parse_ical_datetime(
+                        value,
+                        tzid.as_deref(),
+                        local_tz,
+                        text.span,
+                    )?);
+                }
+            }
+            "RRULE" => rule = Some(parse_rrule(value, text.span)?),
+            "EXDATE" => exdate_lines.push((value.to_string(), tzid)),
+            "RDATE" => rdate_lines.push((value.to_string(), tzid)),
+            _ => {
+                return Err(
+                    LabeledError::new(format!("Unsupported recurrence property: {name}"))
+                        .with_label("expected DTSTART, RRULE, EXDATE or RDATE", text.span),
+                );
+            }
+        }
+    }
+
+    let Some(dtstart) = dtstart else {
+        return Err(LabeledError::new("Missing DTSTART for recurrence rule")
+            .with_label("pipe in a datetime or add a DTSTART line", text.span));
+    };
+
+    // EXDATE and RDATE values without a TZID are interpreted in the DTSTART zone
+    let mut exdates = vec![];
+    for (values, tzid) in exdate_lines {
+        for value in values.split(',') {
+            exdates.push(
+                parse_ical_datetime(value, tzid.as_deref(), dtstart.time_zone(), text.span)?
+                    .timestamp(),
+            );
+        }
+    }
+    let mut rdates = vec![];
+    for (values, tzid) in rdate_lines {
+        for value in values.split(',') {
+            rdates.push(parse_ical_datetime(
+                value,
+                tzid.as_deref(),
+                dtstart.time_zone(),
+                text.span,
+            )?);
+        }
+    }
+
+    Ok(Recurrence {
+        dtstart,
+        rule,
+        exdates,
+        rdates,
+    })
+}
+
+pub fn parse_rrule(rule: &str, span: NuSpan) -> Result<RecurrenceRule, LabeledError> {
+    let rule_error = |msg: String| {
+        LabeledError::new(format!("Invalid RRULE: {msg}")).with_label(rule.to_string(), span)
+    };
+
+    let mut frequency = None;
+    let mut parsed = RecurrenceRule {
+        frequency: Frequency::Yearly,
+        interval: 1,
+        count: None,
+        until: None,
+        by_month: vec![],
+        by_week_no: vec![],
+        by_year_day: vec![],
+        by_month_day: vec![],
+        by_day: vec![],
+        by_hour: vec![],
+        by_minute: vec![],
+        by_second: vec![],
+        by_set_pos: vec![],
+        week_start: Weekday::Monday,
+    };
+
+    for part in rule.trim().split(';').filter(|part| !part.is_empty()) {
+        let Some((key, value)) = part.split_once('=') else {
+            return Err(rule_error(format!("expected KEY=VALUE but found {part:?}")));
+        };
+        let value = value.to_uppercase();
+        match key.to_uppercase().as_ref() {
+            "FREQ" => {
+                frequency = Some(match value.as_ref() {
+                    "YEARLY" => Frequency::Yearly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "WEEKLY" => Frequency::Weekly,
+                    "DAILY" => Frequency::Daily,
+                    "HOURLY" => Frequency::Hourly,
+                    "MINUTELY" => Frequency::Minutely,
+                    "SECONDLY" => Frequency::Secondly,
+                    _ => return Err(rule_error(format!("unknown FREQ {value}"))),
+                })
+            }
+            "INTERVAL" => {
+                parsed.interval =
+                    parse_rule_number(&value, 1, i64::from(i32::MAX), key).map_err(rule_error)?
+            }
+            "COUNT" => {
+                parsed.count = Some(
+                    parse_rule_number(&value, 0, i64::from(i32::MAX), key).map_err(rule_error)?,
+                )
+            }
+            "UNTIL" => {
+                parsed.until = Some(if value.ends_with('Z') {
+                    Until::Instant(
+                        parse_ical_datetime(&value, None, &TimeZone::UTC, span)
+                            .map_err(|_| rule_error(format!("invalid UNTIL {value}")))?
+                            .timestamp(),
+                    )
+                } else {
+                    Until::Civil(
+                        parse_ical_civil(&value)
+                            .map_err(|_| rule_error(format!("invalid UNTIL {value}")))?,
+                    )
+                })
+            }
+            "BYMONTH" => {
+                parsed.by_month = parse_rule_list(&value, 1, 12, false, key).map_err(rule_error)?
+            }
+            "BYWEEKNO" => {
+                parsed.by_week_no = parse_rule_list(&value, 1, 53, true, key).map_err(rule_error)?
+            }
+            "BYYEARDAY" => {
+                parsed.by_year_day =
+                    parse_rule_list(&value, 1, 366, true, key).map_err(rule_error)?
+            }
+            "BYMONTHDAY" => {
+                parsed.by_month_day =
+                    parse_rule_list(&value, 1, 31, true, key).map_err(rule_error)?
+            }
+            "BYHOUR" => {
+                parsed.by_hour = parse_rule_list(&value, 0, 23, false, key).map_err(rule_error)?
+            }
+            "BYMINUTE" => {
+                parsed.by_minute = parse_rule_list(&value, 0, 59, false, key).map_err(rule_error)?
+            }
+            "BYSECOND" => {
+                parsed.by_second = parse_rule_list(&value, 0, 59, false, key).map_err(rule_error)?
+            }
+            "BYSETPOS" => {
+                parsed.by_set_pos =
+                    parse_rule_list(&value, 1, 366, true, key).map_err(rule_error)?
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    // weekday codes are two ASCII letters, so only ASCII can be split by byte
+                    if !day.is_ascii() {
+                        return Err(rule_error(format!("invalid BYDAY {day}")));
+                    }
+                    let split_at = day.len().saturating_sub(2);
+                    let (ordinal, weekday) = day.split_at(split_at);
+                    let weekday = parse_ical_weekday(weekday)
+                        .ok_or_else(|| rule_error(format!("invalid BYDAY {day}")))?;
+                    let ordinal = if ordinal.is_empty() {
+                        None
+                    } else {
+                        let ordinal: i8 = ordinal
+                            .parse()
+                            .map_err(|_| rule_error(format!("invalid BYDAY {day}")))?;
+                        if ordinal == 0 || !(-53..=53).contains(&ordinal) {
+                            return Err(rule_error(format!("invalid BYDAY {day}")));
+                        }
+                        Some(ordinal)
+                    };
+                    parsed.by_day.push((ordinal, weekday));
+                }
+            }
+            "WKST" => {
+                parsed.week_start = parse_ical_weekday(&value)
+                    .ok_or_else(|| rule_error(format!("invalid WKST {value}")))?
+            }
+            _ => return Err(rule_error(format!("unsupported rule part {key}"))),
+        }
+    }
+
+    let Some(frequency) = frequency else {
+        return Err(rule_error("FREQ is required".to_string()));
+    };
+    if parsed.count.is_some() && parsed.until.is_some() {
+        return Err(rule_error("COUNT and UNTIL can't both be used".to_string()));
+    }
+    parsed.frequency = frequency;
+    Ok(parsed)
+}
+
+fn parse_rule_number(value: &str, min: i64, max: i64, key: &str) -> Result<i64, String> {
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|number| (min..=max).contains(number))
+        .ok_or_else(|| format!("invalid {key} {value}"))
+}
+
+fn parse_rule_list<T: TryFrom<i64>>(
+    value: &str,
+    min: i64,
+    max: i64,
+    allow_negative: bool,
+    key: &str,
+) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(|item| {
+            let number = item
+                .parse::<i64>()
+                .map_err(|_| format!("invalid {key} {item}"))?;
+            let valid =
+                (min..=max).contains(&number) || (allow_negative && (min..=max).contains(&-number));
+            if !valid {
+                return Err(format!("invalid {key} {item}"));
+            }
+            T::try_from(number).map_err(|_| format!("invalid {key} {item}"))
+        })
+        .collect()
+}
+
+fn parse_ical_weekday(weekday: &str) -> Option<Weekday> {
+    match weekday {
+        "MO" => Some(Weekday::Monday),
+        "TU" => Some(Weekday::Tuesday),
+        "WE" => Some(Weekday::Wednesday),
+        "TH" => Some(Weekday::Thursday),
+        "FR" => Some(Weekday::Friday),
+        "SA" => Some(Weekday::Saturday),
+        "SU" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+fn parse_ical_civil(value: &str) -> Result<civil::DateTime, jiff::Error> {
+    let value = value.trim_end_matches('Z');
+    if value.len() == 8 {
+        Ok(civil::Date::strptime("%Y%m%d", value)?.to_datetime(civil::Time::MAX))
+    } else {
+        civil::DateTime::strptime("%Y%m%dT%H%M%S", value)
+    }
+}
+
+// Parse an iCalendar DATE or DATE-TIME. UTC values end with Z, values with a TZID are
+// in that zone and floating values are in floating_tz.
+fn parse_ical_datetime(
+    value: &str,
+    tzid: Option<&str>,
+    floating_tz: &TimeZone,
+    span: NuSpan,
+) -> Result<Zoned, LabeledError> {
+    let time_zone = if value.ends_with('Z') {
+        TimeZone::UTC
+    } else if let Some(tzid) = tzid {
+        time_zone_database().get(tzid).map_err(|err| {
+            LabeledError::new(format!("Unknown TZID {tzid}: {err}")).with_label("here", span)
+        })?
+    } else {
+        floating_tz.clone()
+    };
+    let value = value.trim();
+    let datetime = if value.len() == 8 {
+        civil::Date::strptime("%Y%m%d", value).map(|date| date.to_datetime(civil::Time::midnight()))
+    } else {
+        parse_ical_civil(value)
+    }
+    .map_err(|err| {
+        LabeledError::new(format!("Invalid iCalendar datetime {value}: {err}"))
+            .with_label("here", span)
+    })?;
+    datetime
+        .to_zoned(time_zone)
+        .map_err(|err| LabeledError::new(err.to_string()))
+}
+
+// Stream the full recurrence set: the DTSTART, the RRULE occurrences and any RDATEs,
+// in order, without duplicates and without EXDATEs
+pub fn recurrence_set(
+    recurrence: Recurrence,
+    signals: Signals,
+) -> impl Iterator<Item = Result<Zoned, LabeledError>> + Send + 'static {
+    let Recurrence {
+        dtstart,
+        rule,
+        exdates,
+        mut rdates,
+    } = recurrence;
+    rdates.sort();
+    let mut rdates: VecDeque<Zoned> = rdates.into();
+
+    let mut occurrences: Box<dyn Iterator<Item = Result<Zoned, LabeledError>> + Send> = match rule {
+        Some(rule) => Box::new(RruleIter::new(rule, dtstart, signals)),
+        None => Box::new(std::iter::once(Ok(dtstart))),
+    };
+    let mut next_occurrence = occurrences.next();
+    let mut last: Option<Timestamp> = None;
+
+    std::iter::from_fn(move || {
+        loop {
+            let take_rdate = match (&next_occurrence, rdates.front()) {
+                (_, None) => false,
+                (Some(Ok(occurrence)), Some(rdate)) => rdate < occurrence,
+                (Some(Err(_)), Some(_)) => false,
+                (None, Some(_)) => true,
+            };
+            let candidate = if take_rdate {
+                rdates.pop_front().map(Ok)
+            } else {
+                std::mem::replace(&mut next_occurrence, occurrences.next())
+            }?;
+            match candidate {
+                Ok(candidate) => {
+                    let timestamp = candidate.timestamp();
+                    if last.is_some_and(|last| timestamp <= last) || exdates.contains(&timestamp) {
+                        continue;
+                    }
+                    last = Some(timestamp);
+                    return Some(Ok(candidate));
+                }
+                Err(err) => {
+                    rdates.clear();
+                    next_occurrence = None;
+                    return Some(Err(err));
+                }
+            }
+        }
+    })
+}
+
+// Expands an RRULE one FREQ period at a time. Every period's candidate local
+// datetimes are built by filtering the days of the period through the BYxxx rules,
+// which gives the same results as the expand/limit table in RFC 5545.
+struct RruleIter {
+    rule: RecurrenceRule,
+    dtstart: Zoned,
+    signals: Signals,
+    period: i64,
+    emitted: i64,
+    buffer: VecDeque<Zoned>,
+    done: bool,
+}
+
+impl RruleIter {
+    fn new(rule: RecurrenceRule, dtstart: Zoned, signals: Signals) -> RruleIter {
+        let mut iter = RruleIter {
+            rule,
+            dtstart: dtstart.clone(),
+            signals,
+            period: 0,
+            emitted: 0,
+            buffer: VecDeque::new(),
+            done: false,
+        };
+        // DTSTART is always the first occurrence
+        iter.buffer.push_back(dtstart);
+        iter
+    }
+
+    fn period_candidates(&self) -> Result<Vec<Zoned>, LabeledError> {
+        let rule = &self.rule;
+        let start = self.dtstart.datetime();
+        let steps = self
+            .period
+            .checked_mul(rule.interval)
+            .ok_or_else(|| LabeledError::new("recurrence overflowed"))?;
+        let to_error = |err: jiff::Error| LabeledError::new(err.to_string());
+
+        let mut candidates: Vec<civil::DateTime> = vec![];
+        let mut preferred_offset: Option<Offset> = None;
+        if rule.frequency >= Frequency::Daily {
+            let dates: Vec<civil::Date> = match rule.frequency {
+                Frequency::Yearly => {
+                    let first = civil::Date::new(start.year(), 1, 1)
+                        .map_err(to_error)?
+                        .checked_add(steps.years())
+                        .map_err(to_error)?;
+                    days_from(first, i64::from(first.days_in_year()))?
+                }
+                Frequency::Monthly => {
+                    let first = start
+                        .date()
+                        .first_of_month()
+                        .checked_add(steps.months())
+                        .map_err(to_error)?;
+                    days_from(first, i64::from(first.days_in_month()))?
+                }
+                Frequency::Weekly => {
+                    let days_into_week = (start.weekday().to_monday_zero_offset()
+                        - rule.week_start.to_monday_zero_offset())
+                    .rem_euclid(7);
+                    let first = start
+                        .date()
+                        .checked_sub(i64::from(days_into_week).days())
+                        .and_then(|date| date.checked_add(steps.weeks()))
+                        .map_err(to_error)?;
+                    days_from(first, 7)?
+                }
+                _ => vec![start.date().checked_add(steps.days()).map_err(to_error)?],
+            };
+
+            let hours = or_default(&rule.by_hour, start.hour());
+            let minutes = or_default(&rule.by_minute, start.minute());
+            let seconds = or_default(&rule.by_second, start.second());
+            for date in dates.into_iter().filter(|date| self.date_matches(*date)) {
+                for hour in &hours {
+                    for minute in &minutes {
+                        for second in &seconds {
+                            let time = civil::Time::new(
+                                *hour,
+                                *minute,
+                                *second,
+                                start.subsec_nanosecond(),
+                            )
+                            .map_err(to_error)?;
+                            candidates.push(date.to_datetime(time));
+                        }
+                    }
+                }
+            }
+        } else {
+            // Sub-daily periods are stepped in elapsed time so DST changes don't
+            // repeat or skip periods, then limited by local time
+            let period_length = match rule.frequency {
+                Frequency::Hourly => steps.hours(),
+                Frequency::Minutely => steps.minutes(),
+                _ => steps.seconds(),
+            };
+            let period_zoned = self.dtstart.checked_add(period_length).map_err(to_error)?;
+            preferred_offset = Some(period_zoned.offset());
+            let period_start = period_zoned.datetime();
+            if self.date_matches(period_start.date())
+                && (rule.by_hour.is_empty() || rule.by_hour.contains(&period_start.hour()))
+            {
+                let minutes = if rule.frequency == Frequency::Hourly {
+                    or_default(&rule.by_minute, start.minute())
+                } else if rule.by_minute.is_empty()
+                    || rule.by_minute.contains(&period_start.minute())
+                {
+                    vec![period_start.minute()]
+                } else {
+                    vec![]
+                };
+                let seconds = if rule.frequency == Frequency::Secondly {
+                    if rule.by_second.is_empty() || rule.by_second.contains(&period_start.second())
+                    {
+                        vec![period_start.second()]
+                    } else {
+                        vec![]
+                    }
+                } else {
+                    or_default(&rule.by_second, start.second())
+                };
+                for minute in &minutes {
+                    for second in &seconds {
+                        let time = civil::Time::new(
+                            period_start.hour(),
+                            *minute,
+                            *second,
+                            start.subsec_nanosecond(),
+                        )
+                        .map_err(to_error)?;
+                        candidates.push(period_start.date().to_datetime(time));
+                    }
+                }
+            }
+        }
+
+        candidates.sort();
+        candidates.dedup();
+        if !rule.by_set_pos.is_empty() {
+            let len = candidates.len() as i32;
+            let mut selected: Vec<civil::DateTime> = rule
+                .by_set_pos
+                .iter()
+                .filter_map(|pos| {
+                    let index = if *pos > 0 { pos - 1 } else { len + pos };
+                    (0..len)
+                        .contains(&index)
+                        .then(|| candidates[index as usize])
+                })
+                .collect();
+            selected.sort();
+            selected.dedup();
+            candidates = selected;
+        }
+
+        // Nonexistent local times are moved forward by the length of the gap and
+        // repeated local times use the first occurrence, as RFC 5545 requires.
+        // Sub-daily periods keep the offset they were stepped to so both hours of a
+        // fold are produced.
+        let time_zone = self.dtstart.time_zone();
+        let mut zoned = candidates
+            .into_iter()
+            .map(|datetime| {
+                let ambiguous = match preferred_offset {
+                    Some(offset) => {
+                        OffsetConflict::PreferOffset.resolve(datetime, offset, time_zone.clone())
+                    }
+                    None => Ok(time_zone.to_ambiguous_zoned(datetime)),
+                };
+                ambiguous
+                    .and_then(|ambiguous| ambiguous.compatible())
+                    .map_err(to_error)
+            })
+            .collect::<Result<Vec<Zoned>, LabeledError>>()?;
+        zoned.sort();
+        Ok(zoned)
+    }
+
+    fn date_matches(&self, date: civil::Date) -> bool {
+        let rule = &self.rule;
+        let start = self.dtstart.date();
+
+        if !rule.by_month.is_empty() && !rule.by_month.contains(&date.month()) {
+            return false;
+        }
+        if !rule.by_week_no.is_empty() {
+            let week = date.iso_week_date().week();
+            let weeks_in_year = civil::date(date.iso_week_date().year(), 12, 28)
+                .iso_week_date()
+                .week();
+            if !matches_ordinal(&rule.by_week_no, week, weeks_in_year) {
+                return false;
+            }
+        }
+        if !rule.by_year_day.is_empty()
+            && !matches_ordinal(&rule.by_year_day, date.day_of_year(), date.days_in_year())
+        {
+            return false;
+        }
+        if !rule.by_month_day.is_empty()
+            && !matches_ordinal(&rule.by_month_day, date.day(), date.days_in_month())
+        {
+            return false;
+        }
+        if !rule.by_day.is_empty() {
+            // Ordinals are within the month for MONTHLY rules and YEARLY rules with
+            // BYMONTH, within the year for other YEARLY rules and ignored otherwise
+            let matched = rule.by_day.iter().any(|(ordinal, weekday)| {
+                if date.weekday() != *weekday {
+                    return false;
+                }
+                let Some(ordinal) = ordinal else {
+                    return true;
+                };
+                match rule.frequency {
+                    Frequency::Monthly => matches_nth_weekday(
+                        *ordinal,
+                        date.day().into(),
+                        date.days_in_month().into(),
+                    ),
+                    Frequency::Yearly if !rule.by_month.is_empty() => matches_nth_weekday(
+                        *ordinal,
+                        date.day().into(),
+                        date.days_in_month().into(),
+                    ),
+                    Frequency::Yearly if rule.by_week_no.is_empty() => {
+                        matches_nth_weekday(*ordinal, date.day_of_year(), date.days_in_year())
+                    }
+                    _ => true,
+                }
+            });
+            if !matched {
+                return false;
+            }
+        }
+
+        // Fill in the parts of DTSTART the rule doesn't specify
+        let no_day_rules = rule.by_week_no.is_empty()
+            && rule.by_year_day.is_empty()
+            && rule.by_month_day.is_empty()
+            && rule.by_day.is_empty();
+        match rule.frequency {
+            Frequency::Yearly if no_day_rules => {
+                (!rule.by_month.is_empty() || date.month() == start.month())
+                    && date.day() == start.day()
+            }
+            Frequency::Yearly
+                if !rule.by_week_no.is_empty()
+                    && rule.by_year_day.is_empty()
+                    && rule.by_month_day.is_empty()
+                    && rule.by_day.is_empty() =>
+            {
+                date.weekday() == start.weekday()
+            }
+            Frequency::Monthly if rule.by_month_day.is_empty() && rule.by_day.is_empty() => {
+                date.day() == start.day()
+            }
+            Frequency::Weekly if rule.by_day.is_empty() => date.weekday() == start.weekday(),
+            _ => true,
+        }
+    }
+
+    fn is_past_until(&self, occurrence: &Zoned) -> bool {
+        match &self.rule.until {
+            Some(Until::Instant(until)) => occurrence.timestamp() > *until,
+            Some(Until::Civil(until)) => occurrence.datetime() > *until,
+            None => false,
+        }
+    }
+}
+
+impl Iterator for RruleIter {
+    type Item = Result<Zoned, LabeledError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+            if let Some(occurrence) = self.buffer.pop_front() {
+                if self.is_past_until(&occurrence)
+                    || self.rule.count.is_some_and(|count| self.emitted >= count)
+                {
+                    self.done = true;
+                    return None;
+                }
+                self.emitted += 1;
+                return Some(Ok(occurrence));
+            }
+            if self.signals.interrupted() {
+                self.done = true;
+                return None;
+            }
+
+            let candidates = match self.period_candidates() {
+                Ok(candidates) => candidates,
+                Err(err) => {
+                    // Running off the end of the supported calendar just ends the rule
+                    self.done = true;
+                    return (self.period == 0).then_some(Err(err));
+                }
+            };
+            self.period += 1;
+            self.buffer.extend(
+                candidates
+                    .into_iter()
+                    .filter(|candidate| *candidate > self.dtstart),
+            );
+        }
+    }
+}
+
+fn days_from(first: civil::Date, count: i64) -> Result<Vec<civil::Date>, LabeledError> {
+    (0..count)
+        .map(|offset| {
+            first
+                .checked_add(offset.days())
+                .map_err(|err| LabeledError::new(err.to_string()))
+        })
+        .collect()
+}
+
+fn or_default(values: &[i8], default: i8) -> Vec<i8> {
+    if values.is_empty() {
+        vec![default]
+    } else {
+        let mut values = values.to_vec();
+        values.sort();
+        values.dedup();
+        values
+    }
+}
+
+fn matches_ordinal<T: Copy + Into<i16>>(
+    ordinals: &[T],
+    value: impl Into<i16>,
+    len: impl Into<i16>,
+) -> bool {
+    let value = value.into();
+    let len = len.into();
+    ordinals.iter().any(|ordinal| {
+        let ordinal: i16 = (*ordinal).into();
+        if ordinal > 0 {
+            ordinal == value
+        } else {
+            len + ordinal + 1 == value
+        }
+    })
+}
+
+fn matches_nth_weekday(ordinal: i8, day: i16, days_in_period: i16) -> bool {
+    if ordinal > 0 {
+        (day - 1) / 7 + 1 == i16::from(ordinal)
+    } else {
+        -((days_in_period - day) / 7 + 1) == i16::from(ordinal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    fn expand(dtstart: &str, rule: &str, limit: usize) -> Vec<String> {
+        let text = Spanned {
+            item: rule.to_string(),
+            span: NuSpan::unknown(),
+        };
+        let recurrence =
+            parse_recurrence_text(&text, Some(dtstart.parse().unwrap()), &TimeZone::system())
+                .unwrap();
+        recurrence_set(recurrence, Signals::empty())
+            .take(limit)
+            .map(|occurrence| occurrence.unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtRrule)
+    }
+
+    #[test]
+    fn test_rrule_daily_keeps_local_time_across_dst() {
+        assert_eq!(
+            expand(
+                "2024-03-09T09:00:00-05:00[America/New_York]",
+                "FREQ=DAILY;COUNT=3",
+                10
+            ),
+            vec![
+                "2024-03-09T09:00:00-05:00[America/New_York]",
+                "2024-03-10T09:00:00-04:00[America/New_York]",
+                "2024-03-11T09:00:00-04:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_nonexistent_local_time_moves_forward() {
+        assert_eq!(
+            expand(
+                "2024-03-09T02:30:00-05:00[America/New_York]",
+                "FREQ=DAILY;COUNT=2",
+                10
+            ),
+            vec![
+                "2024-03-09T02:30:00-05:00[America/New_York]",
+                "2024-03-10T03:30:00-04:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_yearly_by_month_and_day() {
+        // US Thanksgiving
+        assert_eq!(
+            expand(
+                "2024-11-28T00:00:00-05:00[America/New_York]",
+                "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH",
+                3
+            ),
+            vec![
+                "2024-11-28T00:00:00-05:00[America/New_York]",
+                "2025-11-27T00:00:00-05:00[America/New_York]",
+                "2026-11-26T00:00:00-05:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_weekly_interval_with_until() {
+        assert_eq!(
+            expand(
+                "2024-01-02T09:00:00+00:00[UTC]",
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;UNTIL=20240118T090000Z",
+                10
+            ),
+            vec![
+                "2024-01-02T09:00:00+00:00[UTC]",
+                "2024-01-04T09:00:00+00:00[UTC]",
+                "2024-01-16T09:00:00+00:00[UTC]",
+                "2024-01-18T09:00:00+00:00[UTC]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_monthly_by_month_day_skips_short_months() {
+        assert_eq!(
+            expand(
+                "2024-01-31T12:00:00+00:00[UTC]",
+                "FREQ=MONTHLY;BYMONTHDAY=31;COUNT=3",
+                10
+            ),
+            vec![
+                "2024-01-31T12:00:00+00:00[UTC]",
+                "2024-03-31T12:00:00+00:00[UTC]",
+                "2024-05-31T12:00:00+00:00[UTC]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_hourly_across_fall_back() {
+        assert_eq!(
+            expand(
+                "2024-11-03T00:30:00-04:00[America/New_York]",
+                "FREQ=HOURLY;COUNT=3",
+                10
+            ),
+            vec![
+                "2024-11-03T00:30:00-04:00[America/New_York]",
+                "2024-11-03T01:30:00-04:00[America/New_York]",
+                "2024-11-03T01:30:00-05:00[America/New_York]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_rdate_and_exdate() {
+        assert_eq!(
+            expand(
+                "2024-01-01T09:00:00+00:00[UTC]",
+                "RRULE:FREQ=DAILY;COUNT=3\nEXDATE:20240102T090000Z\nRDATE:20240110T090000Z",
+                10
+            ),
+            vec![
+                "2024-01-01T09:00:00+00:00[UTC]",
+                "2024-01-03T09:00:00+00:00[UTC]",
+                "2024-01-10T09:00:00+00:00[UTC]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_invalid() {
+        let text = Spanned {
+            item: "FREQ=FORTNIGHTLY".to_string(),
+            span: NuSpan::unknown(),
+        };
+        assert!(parse_recurrence_text(&text, Some(Zoned::now()), &TimeZone::system()).is_err());
+    }
+
+    #[test]
+    fn test_rrule_non_ascii_by_day() {
+        for rule in ["FREQ=WEEKLY;COUNT=2;BYDAY=ÉA", "FREQ=MONTHLY;BYDAY=1Mö,TU"] {
+            let err = parse_rrule(rule, NuSpan::unknown()).unwrap_err();
+            assert!(err.msg.contains("invalid BYDAY"), "{}", err.msg);
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use super::business_days::{
+    BusinessCalendar, business_calendar_flags, get_business_calendar_from_call,
+};
+use super::tzdb::local_time_zone;
+use super::utils::get_zoned_from_value;
+use crate::DtPlugin;
+use jiff::{ToSpan, civil};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+pub struct DtSchedule;
+
+impl SimplePluginCommand for DtSchedule {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt schedule"
+    }
+
+    fn signature(&self) -> Signature {
+        business_calendar_flags(
+            Signature::build(self.name())
+                .required(
+                    "effective",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date the schedule starts on.",
+                )
+                .required(
+                    "termination",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date the schedule ends on.",
+                )
+                .named(
+                    "frequency",
+                    SyntaxShape::String,
+                    "How often dates occur: monthly, quarterly (default), semiannual or annual.",
+                    Some('f'),
+                )
+                .named(
+                    "stub",
+                    SyntaxShape::String,
+                    "Where an irregular period goes: short-back (default), long-back, short-front or long-front.",
+                    Some('s'),
+                )
+                .named(
+                    "roll",
+                    SyntaxShape::String,
+                    "Business day convention: modified-following (default), following, preceding, modified-preceding or none.",
+                    Some('r'),
+                ),
+        )
+        .switch(
+            "eom",
+            "Keep dates on the last day of the month when the schedule starts from a month end",
+            Some('e'),
+        )
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Generate a payment or coupon schedule between an effective and a termination date"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Regular dates are counted from the effective date when the stub is at the back and from the termination date when it's at the front. A long stub joins the irregular period onto its neighbor. Each row has the unadjusted date and the date adjusted with the roll convention against --weekend, --holidays and --calendar."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "date",
+            "finance",
+            "coupon",
+            "payment",
+            "swap",
+            "business day",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt schedule 2024-01-15 2024-10-15",
+                description: "Generate a quarterly schedule rolled with modified following",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-01-15"),
+                        "adjusted" => Value::test_string("2024-01-15"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-04-15"),
+                        "adjusted" => Value::test_string("2024-04-15"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-07-15"),
+                        "adjusted" => Value::test_string("2024-07-15"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-10-15"),
+                        "adjusted" => Value::test_string("2024-10-15"),
+                    }),
+                ])),
+            },
+            Example {
+                example: "dt schedule 2024-01-31 2024-06-30 --frequency monthly --eom --roll following",
+                description: "Generate a month end schedule rolled forward over weekends",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-01-31"),
+                        "adjusted" => Value::test_string("2024-01-31"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-02-29"),
+                        "adjusted" => Value::test_string("2024-02-29"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-03-31"),
+                        "adjusted" => Value::test_string("2024-04-01"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-04-30"),
+                        "adjusted" => Value::test_string("2024-04-30"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-05-31"),
+                        "adjusted" => Value::test_string("2024-05-31"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-06-30"),
+                        "adjusted" => Value::test_string("2024-07-01"),
+                    }),
+                ])),
+            },
+            Example {
+                example: "dt schedule 2024-02-10 2025-01-01 --frequency semiannual --stub short-front --calendar us",
+                description: "Generate a semiannual schedule with a short first period rolled over US holidays",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-02-10"),
+                        "adjusted" => Value::test_string("2024-02-12"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2024-07-01"),
+                        "adjusted" => Value::test_string("2024-07-01"),
+                    }),
+                    Value::test_record(record! {
+                        "unadjusted" => Value::test_string("2025-01-01"),
+                        "adjusted" => Value::test_string("2025-01-02"),
+                    }),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let effective_value: Value = call.req(0)?;
+        let termination_value: Value = call.req(1)?;
+        let months = match call.get_flag("frequency")? {
+            Some(frequency) => get_frequency_months_from_string(frequency)?,
+            None => 3,
+        };
+        let stub = match call.get_flag("stub")? {
+            Some(stub) => get_stub_from_string(stub)?,
+            None => Stub::ShortBack,
+        };
+        let roll = match call.get_flag("roll")? {
+            Some(roll) => get_roll_convention_from_string(roll)?,
+            None => RollConvention::ModifiedFollowing,
+        };
+        let end_of_month = call.has_flag("eom")?;
+        let calendar = get_business_calendar_from_call(engine, call)?;
+
+        let effective = get_zoned_from_value(&effective_value, &local_tz)?.date();
+        let termination = get_zoned_from_value(&termination_value, &local_tz)?.date();
+        if termination <= effective {
+            return Err(
+                LabeledError::new("The termination date must be after the effective date")
+                    .with_label(
+                        format!("{termination} isn't after {effective}"),
+                        termination_value.span(),
+                    ),
+            );
+        }
+
+        let dates = generate_schedule(effective, termination, months, stub, end_of_month)?;
+        let rows = dates
+            .into_iter()
+            .map(|date| {
+                let adjusted = roll_date(&calendar, date, roll)?;
+                Ok(Value::record(
+                    record! {
+                        "unadjusted" => Value::string(date.to_string(), call.head),
+                        "adjusted" => Value::string(adjusted.to_string(), call.head),
+                    },
+                    call.head,
+                ))
+            })
+            .collect::<Result<Vec<Value>, LabeledError>>()?;
+        Ok(Value::list(rows, call.head))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stub {
+    ShortFront,
+    LongFront,
+    ShortBack,
+    LongBack,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RollConvention {
+    None,
+    Following,
+    ModifiedFollowing,
+    Preceding,
+    ModifiedPreceding,
+}
+
+fn get_frequency_months_from_string(frequency: Spanned<String>) -> Result<i32, LabeledError> {
+    match frequency.item.to_lowercase().as_ref() {
+        "monthly" | "1m" => Ok(1),
+        "quarterly" | "3m" => Ok(3),
+        "semiannual" | "semiannually" | "6m" => Ok(6),
+        "annual" | "annually" | "yearly" | "12m" | "1y" => Ok(12),
+        _ => Err(
+            LabeledError::new(format!("Invalid frequency: {}", frequency.item)).with_label(
+                "expected monthly, quarterly, semiannual or annual",
+                frequency.span,
+            ),
+        ),
+    }
+}
+
+fn get_stub_from_string(stub: Spanned<String>) -> Result<Stub, LabeledError> {
+    match stub.item.to_lowercase().as_ref() {
+        "short-front" | "short-initial" => Ok(Stub::ShortFront),
+        "long-front" | "long-initial" => Ok(Stub::LongFront),
+        "short-back" | "short-final" => Ok(Stub::ShortBack),
+        "long-back" | "long-final" => Ok(Stub::LongBack),
+        _ => Err(
+            LabeledError::new(format!("Invalid stub: {}", stub.item)).with_label(
+                "expected short-front, long-front, short-back or long-back",
+                stub.span,
+            ),
+        ),
+    }
+}
+
+pub fn get_roll_convention_from_string(
+    roll: Spanned<String>,
+) -> Result<RollConvention, LabeledError> {
+    match roll.item.to_lowercase().as_ref() {
+        "none" | "unadjusted" => Ok(RollConvention::None),
+        "following" | "f" => Ok(RollConvention::Following),
+        "modified-following" | "modified following" | "mf" => Ok(RollConvention::ModifiedFollowing),
+        "preceding" | "p" => Ok(RollConvention::Preceding),
+        "modified-preceding" | "modified preceding" | "mp" => Ok(RollConvention::ModifiedPreceding),
+        _ => Err(
+            LabeledError::new(format!("Invalid roll convention: {}", roll.item)).with_label(
+                "expected following, modified-following, preceding, modified-preceding or none",
+                roll.span,
+            ),
+        ),
+    }
+}
+
+// Regular dates are always offset from the anchor rather than from the previous
+// date, so a schedule from the 31st goes back to the 31st after a short month
+pub fn generate_schedule(
+    effective: civil::Date,
+    termination: civil::Date,
+    months: i32,
+    stub: Stub,
+    end_of_month: bool,
+) -> Result<Vec<civil::Date>, LabeledError> {
+    let backwards = matches!(stub, Stub::ShortFront | Stub::LongFront);
+    let (anchor, step) = if backwards {
+        (termination, -months)
+    } else {
+        (effective, months)
+    };
+    let month_end = end_of_month && anchor.day() == anchor.days_in_month();
+
+    let mut dates = vec![anchor];
+    for periods in 1.. {
+        let date = anchor
+            .checked_add((periods * step).months())
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        let date = if month_end {
+            date.last_of_month()
+        } else {
+            date
+        };
+        if (backwards && date <= effective) || (!backwards && date >= termination) {
+            break;
+        }
+        dates.push(date);
+    }
+
+    // Whatever is left between the last regular date and the far end is the stub,
+    // a long stub drops the regular date next to it to join the two periods
+    let far_end = if backwards { effective } else { termination };
+    let has_stub = dates.last() != Some(&far_end);
+    if has_stub && matches!(stub, Stub::LongFront | Stub::LongBack) && dates.len() > 1 {
+        dates.pop();
+    }
+    dates.push(far_end);
+    if backwards {
+        dates.reverse();
+    }
+    Ok(dates)
+}
+
+// Move a date that isn't a business day with a business day convention
+pub fn roll_date(
+    calendar: &BusinessCalendar,
+    date: civil::Date,
+    roll: RollConvention,
+) -> Result<civil::Date, LabeledError> {
+    let step_to_business_day = |step: i64| -> Result<civil::Date, LabeledError> {
+        let mut rolled = date;
+        while !calendar.is_business_day(rolled) {
+            rolled = rolled
+                .checked_add(step.days())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+        }
+        Ok(rolled)
+    };
+    match roll {
+        RollConvention::None => Ok(date),
+        RollConvention::Following => step_to_business_day(1),
+        RollConvention::Preceding => step_to_business_day(-1),
+        RollConvention::ModifiedFollowing => {
+            let rolled = step_to_business_day(1)?;
+            if rolled.month() == date.month() {
+                Ok(rolled)
+            } else {
+                step_to_business_day(-1)
+            }
+        }
+        RollConvention::ModifiedPreceding => {
+            let rolled = step_to_business_day(-1)?;
+            if rolled.month() == date.month() {
+                Ok(rolled)
+            } else {
+                step_to_business_day(1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtSchedule)
+    }
+
+    #[test]
+    fn test_schedule_stubs() {
+        let (effective, termination) = (date(2024, 1, 10), date(2024, 11, 1));
+        assert_eq!(
+            generate_schedule(effective, termination, 3, Stub::ShortBack, false).unwrap(),
+            vec![
+                date(2024, 1, 10),
+                date(2024, 4, 10),
+                date(2024, 7, 10),
+                date(2024, 10, 10),
+                date(2024, 11, 1),
+            ]
+        );
+        assert_eq!(
+            generate_schedule(effective, termination, 3, Stub::LongBack, false).unwrap(),
+            vec![
+                date(2024, 1, 10),
+                date(2024, 4, 10),
+                date(2024, 7, 10),
+                date(2024, 11, 1),
+            ]
+        );
+        assert_eq!(
+            generate_schedule(effective, termination, 3, Stub::ShortFront, false).unwrap(),
+            vec![
+                date(2024, 1, 10),
+                date(2024, 2, 1),
+                date(2024, 5, 1),
+                date(2024, 8, 1),
+                date(2024, 11, 1),
+            ]
+        );
+        assert_eq!(
+            generate_schedule(effective, termination, 3, Stub::LongFront, false).unwrap(),
+            vec![
+                date(2024, 1, 10),
+                date(2024, 5, 1),
+                date(2024, 8, 1),
+                date(2024, 11, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schedule_without_end_of_month_rule() {
+        // Offsets from the anchor go back to the 31st after February
+        assert_eq!(
+            generate_schedule(
+                date(2024, 1, 31),
+                date(2024, 4, 30),
+                1,
+                Stub::ShortBack,
+                false
+            )
+            .unwrap(),
+            vec![
+                date(2024, 1, 31),
+                date(2024, 2, 29),
+                date(2024, 3, 31),
+                date(2024, 4, 30),
+            ]
+        );
+        // Without --eom a month end from the 30th stays on the 30th
+        assert_eq!(
+            generate_schedule(
+                date(2024, 4, 30),
+                date(2024, 7, 31),
+                1,
+                Stub::ShortBack,
+                false
+            )
+            .unwrap(),
+            vec![
+                date(2024, 4, 30),
+                date(2024, 5, 30),
+                date(2024, 6, 30),
+                date(2024, 7, 30),
+                date(2024, 7, 31),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roll_conventions() {
+        let calendar = BusinessCalendar::default();
+        // 2024-08-31 is a Saturday at the end of the month
+        let date = date(2024, 8, 31);
+        assert_eq!(
+            roll_date(&calendar, date, RollConvention::Following).unwrap(),
+            civil::date(2024, 9, 2)
+        );
+        assert_eq!(
+            roll_date(&calendar, date, RollConvention::ModifiedFollowing).unwrap(),
+            civil::date(2024, 8, 30)
+        );
+        assert_eq!(
+            roll_date(&calendar, date, RollConvention::Preceding).unwrap(),
+            civil::date(2024, 8, 30)
+        );
+        // 2024-06-01 is a Saturday at the start of the month
+        let date = civil::date(2024, 6, 1);
+        assert_eq!(
+            roll_date(&calendar, date, RollConvention::ModifiedPreceding).unwrap(),
+            civil::date(2024, 6, 3)
+        );
+        assert_eq!(
+            roll_date(&calendar, date, RollConvention::None).unwrap(),
+            date
+        );
+    }
+}
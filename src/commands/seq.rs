@@ -0,0 +1,300 @@
+use super::tzdb::local_time_zone;
+use super::utils::{
+    MonthOverflow, add_span_with_overflow, get_month_overflow_from_string, get_weekday_from_string,
+    get_zoned_from_value, parse_jiff_span_from_duration_string,
+};
+use crate::DtPlugin;
+use jiff::{Span as JiffSpan, ToSpan, Zoned, civil::Weekday};
+use nu_plugin::{EngineInterface, EvaluatedCall, PluginCommand};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, LabeledError, PipelineData, Signals,
+    Signature, Spanned, SyntaxShape, Type, Value,
+};
+
+pub struct DtSeq;
+
+impl PluginCommand for DtSeq {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt seq"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(PluginCommand::name(self))
+            .input_output_type(Type::Nothing, Type::List(Type::String.into()))
+            .required(
+                "start",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                "Date or datetime to start the sequence at.",
+            )
+            .optional(
+                "end",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                "Date or datetime to end the sequence at.",
+            )
+            .named(
+                "step",
+                SyntaxShape::String,
+                "Duration to step by, e.g. 1d (default), 1m for a month, 1h, 30min or P1W.",
+                Some('s'),
+            )
+            .named(
+                "count",
+                SyntaxShape::Int,
+                "Maximum number of datetimes to return.",
+                Some('c'),
+            )
+            .switch("exclusive", "Exclude the end datetime", Some('e'))
+            .named(
+                "weekdays",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Only return datetimes falling on these weekdays.",
+                Some('w'),
+            )
+            .named(
+                "overflow",
+                SyntaxShape::String,
+                "How to handle month and year steps landing past the end of a month: clamp (default), roll or reject",
+                Some('o'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Stream a sequence of datetimes from a start to an end or count, stepping by a duration"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "range", "sequence", "generate", "series"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt seq '2024-01-31T09:00:00-06:00[America/Chicago]' '2024-04-30T09:00:00-05:00[America/Chicago]' --step 1m",
+                description: "Return the same time on the last days of each month, stepping from the start so days aren't lost to clamping",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-01-31T09:00:00-06:00[America/Chicago]"),
+                    Value::test_string("2024-02-29T09:00:00-06:00[America/Chicago]"),
+                    Value::test_string("2024-03-31T09:00:00-05:00[America/Chicago]"),
+                    Value::test_string("2024-04-30T09:00:00-05:00[America/Chicago]"),
+                ])),
+            },
+            Example {
+                example: "dt seq '2024-08-01T00:00:00-05:00[America/Chicago]' --count 3 --weekdays [sat sun]",
+                description: "Return the first three weekend days starting from a date",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-08-03T00:00:00-05:00[America/Chicago]"),
+                    Value::test_string("2024-08-04T00:00:00-05:00[America/Chicago]"),
+                    Value::test_string("2024-08-10T00:00:00-05:00[America/Chicago]"),
+                ])),
+            },
+            Example {
+                example: "dt seq '2024-03-10T00:00:00-06:00[America/Chicago]' --step 1h --count 3",
+                description: "Return hourly datetimes across a DST change",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-03-10T00:00:00-06:00[America/Chicago]"),
+                    Value::test_string("2024-03-10T01:00:00-06:00[America/Chicago]"),
+                    Value::test_string("2024-03-10T03:00:00-05:00[America/Chicago]"),
+                ])),
+            },
+            Example {
+                example: "dt seq '2024-08-01T12:00:00+00:00[UTC]' '2024-08-01T13:00:00+00:00[UTC]' --step T30m --exclusive",
+                description: "Return half hour marks, excluding the end",
+                result: Some(Value::test_list(vec![
+                    Value::test_string("2024-08-01T12:00:00+00:00[UTC]"),
+                    Value::test_string("2024-08-01T12:30:00+00:00[UTC]"),
+                ])),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let start_value: Value = call.req(0)?;
+        let end_value: Option<Value> = call.opt(1)?;
+        let step_string: Option<Spanned<String>> = call.get_flag("step")?;
+        let count: Option<Spanned<i64>> = call.get_flag("count")?;
+        let exclusive = call.has_flag("exclusive")?;
+        let weekday_strings: Option<Vec<Spanned<String>>> = call.get_flag("weekdays")?;
+        let overflow = get_month_overflow_from_string(call.get_flag("overflow")?)?;
+
+        let start = get_zoned_from_value(&start_value, &local_tz)?;
+        let end = end_value
+            .as_ref()
+            .map(|end_value| get_zoned_from_value(end_value, &local_tz))
+            .transpose()?;
+        let step = match step_string {
+            Some(step_string) => {
+                let step_span = step_string.span;
+                let step = parse_jiff_span_from_duration_string(step_string)?;
+                if step.is_zero() {
+                    return Err(LabeledError::new("step must not be zero")
+                        .with_label("zero length step", step_span));
+                }
+                step
+            }
+            None => 1.day(),
+        };
+        if end.is_none() && count.is_none() {
+            return Err(
+                LabeledError::new("Please provide an end datetime or --count")
+                    .with_label("sequence would never end", call.head),
+            );
+        }
+        let count = match count {
+            Some(count) if count.item < 0 => {
+                return Err(LabeledError::new("count must not be negative")
+                    .with_label(format!("got {}", count.item), count.span));
+            }
+            Some(count) => Some(count.item as usize),
+            None => None,
+        };
+        if let Some(weekday_strings) = &weekday_strings
+            && weekday_strings.is_empty()
+        {
+            let span = call
+                .get_flag_value("weekdays")
+                .map_or(call.head, |value| value.span());
+            return Err(LabeledError::new("weekdays must not be empty")
+                .with_label("no datetime would ever match", span));
+        }
+        let weekdays = weekday_strings
+            .map(|weekdays| {
+                weekdays
+                    .into_iter()
+                    .map(get_weekday_from_string)
+                    .collect::<Result<Vec<Weekday>, LabeledError>>()
+            })
+            .transpose()?;
+
+        let span = call.head;
+        let seq = DatetimeSeq {
+            start,
+            end,
+            step,
+            exclusive,
+            overflow,
+            index: 0,
+            done: false,
+            signals: engine.signals().clone(),
+        }
+        .filter(move |datetime| match (&weekdays, datetime) {
+            (Some(weekdays), Ok(datetime)) => weekdays.contains(&datetime.weekday()),
+            _ => true,
+        })
+        .take(count.unwrap_or(usize::MAX))
+        .map(move |datetime| match datetime {
+            Ok(datetime) => Value::string(datetime.to_string(), span),
+            Err(err) => Value::error(err.into(), span),
+        });
+
+        Ok(seq.into_pipeline_data(span, engine.signals().clone()))
+    }
+}
+
+// Lazily yields start + step * n until the end is passed. Each datetime is computed
+// from the start rather than the previous datetime so month steps don't drift, e.g.
+// Jan 31, Feb 29, Mar 31 instead of Jan 31, Feb 29, Mar 29.
+struct DatetimeSeq {
+    start: Zoned,
+    end: Option<Zoned>,
+    step: JiffSpan,
+    exclusive: bool,
+    overflow: MonthOverflow,
+    index: i64,
+    done: bool,
+    // Checked on every step because a weekday filter can skip datetimes for a long
+    // time before the outer stream sees one
+    signals: Signals,
+}
+
+impl DatetimeSeq {
+    fn nth_datetime(&self) -> Result<Zoned, LabeledError> {
+        let offset = self
+            .step
+            .checked_mul(self.index)
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        add_span_with_overflow(&self.start, offset, self.overflow, false)
+    }
+}
+
+impl Iterator for DatetimeSeq {
+    type Item = Result<Zoned, LabeledError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.signals.interrupted() {
+            return None;
+        }
+
+        let datetime = match self.nth_datetime() {
+            Ok(datetime) => datetime,
+            Err(err) => {
+                self.done = true;
+                return Some(Err(err));
+            }
+        };
+
+        if let Some(end) = &self.end {
+            let past_end = if self.step.is_negative() {
+                datetime < *end || (self.exclusive && datetime == *end)
+            } else {
+                datetime > *end || (self.exclusive && datetime == *end)
+            };
+            if past_end {
+                self.done = true;
+                return None;
+            }
+        }
+
+        self.index += 1;
+        Some(Ok(datetime))
+    }
+}
+
+#[test]
+fn test_examples() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    // This will automatically run the examples specified in your command and compare their actual
+    // output against what was specified in the example.
+    //
+    // We recommend you add this test to any other commands you create, or remove it if the examples
+    // can't be tested this way.
+
+    PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtSeq)
+}
+
+#[test]
+fn test_empty_weekdays() -> Result<(), nu_protocol::ShellError> {
+    use nu_plugin_test_support::PluginTest;
+
+    let result = PluginTest::new("dt", DtPlugin.into())?
+        .eval("dt seq '2024-08-01T00:00:00+00:00[UTC]' --count 1 --weekdays []");
+    assert!(result.is_err_and(|err| err.to_string().contains("weekdays must not be empty")));
+    Ok(())
+}
+
+#[test]
+fn test_seq_stops_when_interrupted() {
+    use std::sync::{Arc, atomic::AtomicBool};
+
+    let mut seq = DatetimeSeq {
+        start: "2024-08-01T00:00:00+00:00[UTC]".parse().unwrap(),
+        end: None,
+        step: 1.week(),
+        exclusive: false,
+        overflow: MonthOverflow::Clamp,
+        index: 0,
+        done: false,
+        signals: Signals::new(Arc::new(AtomicBool::new(true))),
+    };
+    assert!(seq.next().is_none());
+}
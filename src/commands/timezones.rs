@@ -0,0 +1,659 @@
+use super::tzdb::{
+    TimeZoneDatabaseSource, local_now, local_time_zone, time_zone_database,
+    time_zone_database_source,
+};
+use super::utils::{get_time_zone_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{
+    Timestamp,
+    tz::{Dst, Offset},
+};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Span, Spanned, SyntaxShape, Value, record,
+};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+pub struct DtListTimezones;
+
+impl SimplePluginCommand for DtListTimezones {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt list-timezones"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .optional(
+                "search",
+                SyntaxShape::String,
+                "Only list zones whose name contains this text or whose abbreviation is this text.",
+            )
+            .named(
+                "offset",
+                SyntaxShape::String,
+                "Only list zones currently at this UTC offset, e.g. +05:30, -5 or 0.",
+                Some('o'),
+            )
+            .switch(
+                "canonical",
+                "Only list canonical zones, leaving out aliases like US/Central",
+                Some('c'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "List the IANA time zones in the time zone database with their current offset, abbreviation and DST status"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Offsets, abbreviations and DST status are for the piped in datetime, or now when nothing is piped in. Zones that are links to another zone show the zone they link to. Canonical status comes from the tzdata.zi file of the system time zone database in use. It's empty when that file can't be found or the bundled database is in use, and --canonical is an error then."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["time zone", "tz", "iana", "zone", "offset", "abbreviation"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T12:00:00+00:00[UTC]' | dt list-timezones kolkata",
+                description: "Find a time zone by part of its name",
+                // canonical and link_to depend on which time zone database is in use
+                result: None,
+            },
+            Example {
+                example: "dt list-timezones --offset +05:45",
+                description: "Find the time zones currently at a UTC offset",
+                result: None,
+            },
+            Example {
+                example: "dt list-timezones america --canonical | where dst",
+                description: "List the canonical American zones currently observing DST",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let search: Option<String> = call.opt(0)?;
+        let offset: Option<Spanned<String>> = call.get_flag("offset")?;
+        let canonical_only = call.has_flag("canonical")?;
+
+        let offset = offset
+            .map(|offset| {
+                parse_utc_offset(&offset.item).ok_or_else(|| {
+                    LabeledError::new(format!("Invalid UTC offset: {}", offset.item))
+                        .with_label("expected an offset like +05:30, -5 or 0", offset.span)
+                })
+            })
+            .transpose()?;
+        let timestamp = match input {
+            Value::Nothing { .. } => Timestamp::now(),
+            _ => get_zoned_from_value(input, &local_tz)?.timestamp(),
+        };
+        let search = search.map(|search| search.to_lowercase());
+        let links = if canonical_only {
+            Some(get_time_zone_links_for_canonical(call.head)?)
+        } else {
+            get_time_zone_links()
+        };
+
+        let mut rows = vec![];
+        for name in available_time_zone_names() {
+            let link_to = links.as_ref().and_then(|links| links.get(&name));
+            if canonical_only && link_to.is_some() {
+                continue;
+            }
+            let Ok(time_zone) = time_zone_database().get(&name) else {
+                continue;
+            };
+            let info = time_zone.to_offset_info(timestamp);
+            if offset.is_some_and(|offset| offset != info.offset()) {
+                continue;
+            }
+            if let Some(search) = &search
+                && !name.to_lowercase().contains(search)
+                && info.abbreviation().to_lowercase() != *search
+            {
+                continue;
+            }
+            let next_transition = match time_zone.following(timestamp).next() {
+                Some(transition) => Value::string(
+                    transition
+                        .timestamp()
+                        .to_zoned(time_zone.clone())
+                        .to_string(),
+                    call.head,
+                ),
+                None => Value::nothing(call.head),
+            };
+            rows.push(Value::record(
+                record! {
+                    "name" => Value::string(&name, call.head),
+                    "offset" => Value::string(format_utc_offset(info.offset()), call.head),
+                    "abbreviation" => Value::string(info.abbreviation(), call.head),
+                    "dst" => Value::bool(info.dst() == Dst::Yes, call.head),
+                    "next_transition" => next_transition,
+                    "canonical" => match &links {
+                        Some(_) => Value::bool(link_to.is_none(), call.head),
+                        None => Value::nothing(call.head),
+                    },
+                    "link_to" => match link_to {
+                        Some(target) => Value::string(target, call.head),
+                        None => Value::nothing(call.head),
+                    },
+                },
+                call.head,
+            ));
+        }
+        Ok(Value::list(rows, call.head))
+    }
+}
+
+pub struct DtToTimezone;
+
+impl SimplePluginCommand for DtToTimezone {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt to-timezone"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "zone",
+                SyntaxShape::String,
+                "Time zone to convert to: an IANA name, UTC, local, a fixed offset like +05:30, a TZif file path or a POSIX TZ string.",
+            )
+            .switch(
+                "keep-wall-clock",
+                "Keep the date and time of day and reinterpret them in the new time zone instead of keeping the instant",
+                Some('k'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Express the provided date or datetime in another time zone"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Without --keep-wall-clock the result is the same instant with the civil time of the new time zone. With it the civil time stays the same and the instant changes. A wall clock time skipped by a DST gap moves forward by the length of the gap and one repeated by a fold uses the earlier instant. Time zones without an IANA name, like POSIX TZ strings, are written with just their offset in brackets. TZif files under a zoneinfo directory keep the name of their path below it when the time zone database has it, other TZif files are written with just their offset too. local, now when nothing is piped in and datetimes without an offset use the TZ of the nushell session when it's set and the system time zone otherwise."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["time zone", "tz", "convert", "offset", "posix", "iana"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone Asia/Kolkata",
+                description: "Express a datetime in an IANA time zone",
+                result: Some(Value::test_string(
+                    "2024-08-25T21:22:30+05:30[Asia/Kolkata]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone utc",
+                description: "Express a datetime in UTC",
+                result: Some(Value::test_string("2024-08-25T15:52:30+00:00[UTC]")),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone +05:45",
+                description: "Express a datetime at a fixed UTC offset",
+                result: Some(Value::test_string("2024-08-25T21:37:30+05:45[+05:45]")),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone 'EST5EDT,M3.2.0,M11.1.0'",
+                description: "Express a datetime in a time zone given by a POSIX TZ string",
+                result: Some(Value::test_string("2024-08-25T11:52:30-04:00[-04:00]")),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone /usr/share/zoneinfo/Asia/Tokyo",
+                description: "Express a datetime in a time zone loaded from a TZif file",
+                result: None,
+            },
+            Example {
+                example: "$env.TZ = 'Europe/Paris'; '2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone local",
+                description: "Express a datetime in the time zone set by TZ in the session",
+                result: None,
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone Europe/London --keep-wall-clock",
+                description: "Keep the wall clock time and move it to another time zone",
+                result: Some(Value::test_string(
+                    "2024-08-25T10:52:30+01:00[Europe/London]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let time_zone = get_time_zone_from_string(engine, call.req(0)?)?;
+        let keep_wall_clock = call.has_flag("keep-wall-clock")?;
+
+        let datetime = match input {
+            Value::Nothing { .. } => local_now(engine),
+            _ => get_zoned_from_value(input, &local_tz)?,
+        };
+        let result = if keep_wall_clock {
+            datetime
+                .datetime()
+                .to_zoned(time_zone)
+                .map_err(|err| LabeledError::new(err.to_string()))?
+        } else {
+            datetime.with_time_zone(time_zone)
+        };
+        Ok(Value::string(result.to_string(), call.head))
+    }
+}
+
+pub struct DtTzTransitions;
+
+impl SimplePluginCommand for DtTzTransitions {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt tz transitions"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "zone",
+                SyntaxShape::String,
+                "Time zone to list the offset transitions of.",
+            )
+            .named(
+                "until",
+                SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                "List every transition between the provided datetime and this one.",
+                Some('u'),
+            )
+            .named(
+                "count",
+                SyntaxShape::Int,
+                "Number of transitions to list when --until isn't given (default 5).",
+                Some('c'),
+            )
+            .switch(
+                "previous",
+                "List the transitions before the provided datetime instead of after it",
+                Some('p'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "List the DST and UTC offset transitions of a time zone after or before the provided datetime"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Transitions start from the piped in datetime, or now when nothing is piped in. Each row has the first instant with the new offset, the offsets and abbreviations before and after, and whether the transition skips wall clock times (gap), repeats them (fold) or only changes the abbreviation or DST status."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["time zone", "tz", "dst", "daylight saving", "gap", "fold"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-01-01T00:00:00-06:00[America/Chicago]' | dt tz transitions America/Chicago --count 2",
+                description: "List the next two transitions of a time zone",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "instant" => Value::test_string("2024-03-10T03:00:00-05:00[America/Chicago]"),
+                        "offset_before" => Value::test_string("-06:00"),
+                        "offset_after" => Value::test_string("-05:00"),
+                        "abbreviation_before" => Value::test_string("CST"),
+                        "abbreviation_after" => Value::test_string("CDT"),
+                        "kind" => Value::test_string("gap"),
+                    }),
+                    Value::test_record(record! {
+                        "instant" => Value::test_string("2024-11-03T01:00:00-06:00[America/Chicago]"),
+                        "offset_before" => Value::test_string("-05:00"),
+                        "offset_after" => Value::test_string("-06:00"),
+                        "abbreviation_before" => Value::test_string("CDT"),
+                        "abbreviation_after" => Value::test_string("CST"),
+                        "kind" => Value::test_string("fold"),
+                    }),
+                ])),
+            },
+            Example {
+                example: "'2024-01-01T00:00:00+00:00[UTC]' | dt tz transitions Europe/London --until 2025-01-01",
+                description: "List every transition of a time zone between two datetimes",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "instant" => Value::test_string("2024-03-31T02:00:00+01:00[Europe/London]"),
+                        "offset_before" => Value::test_string("+00:00"),
+                        "offset_after" => Value::test_string("+01:00"),
+                        "abbreviation_before" => Value::test_string("GMT"),
+                        "abbreviation_after" => Value::test_string("BST"),
+                        "kind" => Value::test_string("gap"),
+                    }),
+                    Value::test_record(record! {
+                        "instant" => Value::test_string("2024-10-27T01:00:00+00:00[Europe/London]"),
+                        "offset_before" => Value::test_string("+01:00"),
+                        "offset_after" => Value::test_string("+00:00"),
+                        "abbreviation_before" => Value::test_string("BST"),
+                        "abbreviation_after" => Value::test_string("GMT"),
+                        "kind" => Value::test_string("fold"),
+                    }),
+                ])),
+            },
+            Example {
+                example: "dt tz transitions Europe/Moscow --previous --count 3",
+                description: "List the last three transitions of a time zone before now",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let time_zone = get_time_zone_from_string(engine, call.req(0)?)?;
+        let until: Option<Value> = call.get_flag("until")?;
+        let count: Option<Spanned<i64>> = call.get_flag("count")?;
+        let previous = call.has_flag("previous")?;
+
+        let start = match input {
+            Value::Nothing { .. } => Timestamp::now(),
+            _ => get_zoned_from_value(input, &local_tz)?.timestamp(),
+        };
+        let transitions: Vec<Timestamp> = match until {
+            Some(until) => {
+                let end = get_zoned_from_value(&until, &local_tz)?.timestamp();
+                if end >= start {
+                    time_zone
+                        .following(start)
+                        .map(|transition| transition.timestamp())
+                        .take_while(|timestamp| *timestamp <= end)
+                        .collect()
+                } else {
+                    time_zone
+                        .preceding(start)
+                        .map(|transition| transition.timestamp())
+                        .take_while(|timestamp| *timestamp >= end)
+                        .collect()
+                }
+            }
+            None => {
+                let count = match count {
+                    Some(count) if count.item < 0 => {
+                        return Err(LabeledError::new("count can't be negative")
+                            .with_label(format!("got {}", count.item), count.span));
+                    }
+                    Some(count) => count.item as usize,
+                    None => 5,
+                };
+                if previous {
+                    time_zone
+                        .preceding(start)
+                        .map(|transition| transition.timestamp())
+                        .take(count)
+                        .collect()
+                } else {
+                    time_zone
+                        .following(start)
+                        .map(|transition| transition.timestamp())
+                        .take(count)
+                        .collect()
+                }
+            }
+        };
+
+        let rows = transitions
+            .into_iter()
+            .map(|timestamp| {
+                let before = timestamp
+                    .checked_sub(jiff::SignedDuration::from_nanos(1))
+                    .map_err(|err| LabeledError::new(err.to_string()))?;
+                let before = time_zone.to_offset_info(before);
+                let after = time_zone.to_offset_info(timestamp);
+                let kind = match after.offset().seconds().cmp(&before.offset().seconds()) {
+                    Ordering::Greater => "gap",
+                    Ordering::Less => "fold",
+                    Ordering::Equal => "none",
+                };
+                Ok(Value::record(
+                    record! {
+                        "instant" => Value::string(timestamp.to_zoned(time_zone.clone()).to_string(), call.head),
+                        "offset_before" => Value::string(format_utc_offset(before.offset()), call.head),
+                        "offset_after" => Value::string(format_utc_offset(after.offset()), call.head),
+                        "abbreviation_before" => Value::string(before.abbreviation(), call.head),
+                        "abbreviation_after" => Value::string(after.abbreviation(), call.head),
+                        "kind" => Value::string(kind, call.head),
+                    },
+                    call.head,
+                ))
+            })
+            .collect::<Result<Vec<Value>, LabeledError>>()?;
+        Ok(Value::list(rows, call.head))
+    }
+}
+
+// Every zone name in the time zone database, sorted
+pub fn available_time_zone_names() -> Vec<String> {
+    let mut names: Vec<String> = time_zone_database()
+        .available()
+        .map(|name| name.as_str().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+// Read the tzdata.zi source file of the time zone database in use. It's the only
+// place the database says which names are links, and the bundled copy has none.
+pub fn read_tzdata_source() -> Option<String> {
+    match time_zone_database_source() {
+        TimeZoneDatabaseSource::System(directory) => {
+            std::fs::read_to_string(directory.join("tzdata.zi")).ok()
+        }
+        _ => None,
+    }
+}
+
+// Map each alias zone name to the zone it links to, from lines like
+// "L America/Chicago US/Central"
+pub fn get_time_zone_links() -> Option<BTreeMap<String, String>> {
+    let source = read_tzdata_source()?;
+    Some(
+        source
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                match (fields.next(), fields.next(), fields.next()) {
+                    (Some("L"), Some(target), Some(alias)) => {
+                        Some((alias.to_string(), target.to_string()))
+                    }
+                    _ => None,
+                }
+            })
+            .collect(),
+    )
+}
+
+// The links --canonical needs to leave out aliases, which can't be told apart
+// from canonical zones without them
+pub fn get_time_zone_links_for_canonical(
+    span: Span,
+) -> Result<BTreeMap<String, String>, LabeledError> {
+    get_time_zone_links().ok_or_else(|| {
+        LabeledError::new("Can't tell canonical time zones from links")
+            .with_label("needs the tzdata.zi file of the time zone database", span)
+            .with_help("the bundled time zone database has no link data, unset DT_TZDB or set TZDIR to a zoneinfo directory with tzdata.zi")
+    })
+}
+
+// Parse a UTC offset like +05:30, -0800, +5, 5.5 or 0, optionally after UTC or GMT
+pub fn parse_utc_offset(text: &str) -> Option<Offset> {
+    let text = text.trim();
+    let text = ["UTC", "utc", "GMT", "gmt"]
+        .iter()
+        .find_map(|prefix| text.strip_prefix(prefix))
+        .unwrap_or(text);
+    if text.is_empty() || text == "Z" || text == "z" {
+        return Some(Offset::UTC);
+    }
+    let (sign, digits) = match text.as_bytes()[0] {
+        b'+' => (1, &text[1..]),
+        b'-' => (-1, &text[1..]),
+        b'0'..=b'9' => (1, text),
+        _ => return None,
+    };
+    let (hours, minutes, seconds) = if let Some((hours, minutes)) = digits.split_once('.') {
+        // Decimal hours like 5.5 or 5.75
+        let fraction: f64 = format!("0.{minutes}").parse().ok()?;
+        (
+            hours.parse::<i32>().ok()?,
+            (fraction * 60.0).round() as i32,
+            0,
+        )
+    } else if digits.contains(':') {
+        let mut fields = digits.split(':');
+        let hours = fields.next()?.parse().ok()?;
+        let minutes = fields.next()?.parse().ok()?;
+        let seconds = fields
+            .next()
+            .map_or(Some(0), |seconds| seconds.parse().ok())?;
+        if fields.next().is_some() {
+            return None;
+        }
+        (hours, minutes, seconds)
+    } else if digits.len() == 4 && digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        (digits[..2].parse().ok()?, digits[2..].parse().ok()?, 0)
+    } else {
+        (digits.parse().ok()?, 0, 0)
+    };
+    if !(0..60).contains(&minutes) || !(0..60).contains(&seconds) {
+        return None;
+    }
+    Offset::from_seconds(sign * (hours * 3600 + minutes * 60 + seconds)).ok()
+}
+
+// Format a UTC offset as +HH:MM, adding seconds only when the offset has them
+pub fn format_utc_offset(offset: Offset) -> String {
+    let seconds = offset.seconds();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if seconds == 0 {
+        format!("{sign}{hours:02}:{minutes:02}")
+    } else {
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtListTimezones)?;
+        plugin_test.test_command_examples(&DtToTimezone)?;
+        plugin_test.test_command_examples(&DtTzTransitions)
+    }
+
+    #[test]
+    fn test_list_timezones_canonical_follows_database() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        let rows = plugin_test
+            .eval("'2024-08-25T12:00:00+00:00[UTC]' | dt list-timezones kolkata")?
+            .into_value(Span::test_data())?
+            .into_list()?;
+        let kolkata = rows
+            .iter()
+            .find(|row| row.get_data_by_key("name") == Some(Value::test_string("Asia/Kolkata")))
+            .expect("Asia/Kolkata is listed");
+        assert_eq!(
+            kolkata.get_data_by_key("offset"),
+            Some(Value::test_string("+05:30"))
+        );
+        let canonical = kolkata.get_data_by_key("canonical");
+        let listed_canonical = plugin_test.eval("dt list-timezones kolkata --canonical");
+        match get_time_zone_links() {
+            Some(_) => {
+                assert_eq!(canonical, Some(Value::test_bool(true)));
+                assert!(listed_canonical.is_ok());
+            }
+            None => {
+                assert_eq!(canonical, Some(Value::test_nothing()));
+                assert!(
+                    listed_canonical
+                        .and_then(|data| data.into_value(Span::test_data()))
+                        .is_err()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_wall_clock_in_gap() -> Result<(), nu_protocol::ShellError> {
+        // 02:30 doesn't exist in New York on 2024-03-10
+        let result = PluginTest::new("dt", DtPlugin.into())?
+            .eval("'2024-03-10T02:30:00+00:00[UTC]' | dt to-timezone America/New_York --keep-wall-clock")?
+            .into_value(nu_protocol::Span::test_data())?;
+        assert_eq!(
+            result,
+            Value::test_string("2024-03-10T03:30:00-04:00[America/New_York]")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        let seconds = |text| parse_utc_offset(text).map(|offset| offset.seconds());
+        assert_eq!(seconds("+05:30"), Some(19800));
+        assert_eq!(seconds("-0800"), Some(-28800));
+        assert_eq!(seconds("-5"), Some(-18000));
+        assert_eq!(seconds("5.75"), Some(20700));
+        assert_eq!(seconds("UTC+2"), Some(7200));
+        assert_eq!(seconds("0"), Some(0));
+        assert_eq!(seconds("+05:75"), None);
+        assert_eq!(seconds("east"), None);
+    }
+
+    #[test]
+    fn test_format_utc_offset() {
+        assert_eq!(
+            format_utc_offset(Offset::from_seconds(-34200).unwrap()),
+            "-09:30"
+        );
+        assert_eq!(format_utc_offset(Offset::UTC), "+00:00");
+        // Amsterdam's local mean time before 1937
+        assert_eq!(
+            format_utc_offset(Offset::from_seconds(1172).unwrap()),
+            "+00:19:32"
+        );
+    }
+}
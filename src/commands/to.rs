@@ -1,3 +1,4 @@
+use super::tzdb::local_time_zone;
 use super::utils::{ISO8601_STRICT, parse_datetime_string_add_nanos_optionally};
 use crate::DtPlugin;
 use jiff::fmt::rfc2822;
@@ -43,10 +44,11 @@ impl SimplePluginCommand for DtTo {
     fn run(
         &self,
         _plugin: &DtPlugin,
-        _engine: &EngineInterface,
+        engine: &EngineInterface,
         _call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
         // Boilerplate code
         // [ ] dt to-rfc3339
         // [ ] dt to-rfc9557
@@ -57,11 +59,17 @@ impl SimplePluginCommand for DtTo {
         let datetime = match input {
             Value::Date { val, .. } => {
                 // so much easier just to output chrono as rfc 3339 and let jiff parse it
-                parse_datetime_string_add_nanos_optionally(&val.to_rfc3339(), None, span, None)?
+                parse_datetime_string_add_nanos_optionally(
+                    &val.to_rfc3339(),
+                    None,
+                    span,
+                    None,
+                    &local_tz,
+                )?
             }
             Value::String { val, .. } => {
                 // eprintln!("String: {:?}", val);
-                parse_datetime_string_add_nanos_optionally(val, None, span, None)?
+                parse_datetime_string_add_nanos_optionally(val, None, span, None, &local_tz)?
             }
             _ => {
                 return Err(LabeledError::new(
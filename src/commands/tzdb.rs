@@ -0,0 +1,358 @@
+use super::timezones::read_tzdata_source;
+use crate::DtPlugin;
+use jiff::{
+    Timestamp, Zoned,
+    tz::{self, TimeZone, TimeZoneDatabase},
+};
+use nu_path::expand_path_with;
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Value, record};
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+// Environment variable that picks the time zone database when the plugin starts
+const TZDB_ENV_VAR: &str = "DT_TZDB";
+
+pub struct DtTzdb;
+
+impl SimplePluginCommand for DtTzdb {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt tzdb"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name()).category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return which time zone database the plugin is using and its version"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"By default the system copy of the IANA time zone database is used, falling back to the bundled copy when there isn't one. Builds with the bundled-tzdb cargo feature embed the database, and setting DT_TZDB=bundled before the plugin starts uses it even when a system copy exists so results are the same on every machine. Run `plugin stop dt` after changing DT_TZDB."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["time zone", "tz", "iana", "database", "version", "bundled"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt tzdb",
+                description: "Show the time zone database in use",
+                result: None,
+            },
+            Example {
+                example: "$env.DT_TZDB = 'bundled'; plugin stop dt; dt tzdb",
+                description: "Restart the plugin preferring the bundled time zone database",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        _engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let (database, source) = time_zone_database_with_source();
+        let (location, version) = match source {
+            TimeZoneDatabaseSource::System(directory) => {
+                (Some(directory.display().to_string()), system_tzdb_version())
+            }
+            TimeZoneDatabaseSource::Bundled => (None, bundled_tzdb_version()),
+            _ => (None, None),
+        };
+        let optional_string = |value: Option<String>| match value {
+            Some(value) => Value::string(value, call.head),
+            None => Value::nothing(call.head),
+        };
+        Ok(Value::record(
+            record! {
+                "source" => Value::string(source.as_str(), call.head),
+                "location" => optional_string(location),
+                "version" => optional_string(version),
+                "zones" => Value::int(database.available().count() as i64, call.head),
+                "bundled_available" => Value::bool(cfg!(feature = "bundled-tzdb"), call.head),
+                "bundled_version" => optional_string(bundled_tzdb_version()),
+                "preference" => Value::string(
+                    if prefers_bundled_tzdb() { "bundled" } else { "system" },
+                    call.head,
+                ),
+            },
+            call.head,
+        ))
+    }
+}
+
+fn prefers_bundled_tzdb() -> bool {
+    std::env::var(TZDB_ENV_VAR).is_ok_and(|value| value.eq_ignore_ascii_case("bundled"))
+}
+
+// Where the time zone database in use came from
+pub enum TimeZoneDatabaseSource {
+    // A zoneinfo directory like /usr/share/zoneinfo
+    System(PathBuf),
+    Bundled,
+    // What jiff found when there's no zoneinfo directory or bundled copy, which
+    // is only ever Android's concatenated tzdata
+    Concatenated,
+    Unavailable,
+}
+
+impl TimeZoneDatabaseSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TimeZoneDatabaseSource::System(_) => "system",
+            TimeZoneDatabaseSource::Bundled => "bundled",
+            TimeZoneDatabaseSource::Concatenated => "concatenated",
+            TimeZoneDatabaseSource::Unavailable => "unavailable",
+        }
+    }
+}
+
+// The time zone database every zone lookup goes through. It's the system copy
+// found the same way jiff looks for it, unless the bundled copy is compiled in
+// and preferred with DT_TZDB or there's no system copy.
+pub fn time_zone_database() -> &'static TimeZoneDatabase {
+    &time_zone_database_with_source().0
+}
+
+pub fn time_zone_database_source() -> &'static TimeZoneDatabaseSource {
+    &time_zone_database_with_source().1
+}
+
+fn time_zone_database_with_source() -> &'static (TimeZoneDatabase, TimeZoneDatabaseSource) {
+    static DATABASE: OnceLock<(TimeZoneDatabase, TimeZoneDatabaseSource)> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        if cfg!(feature = "bundled-tzdb") && prefers_bundled_tzdb() {
+            return (TimeZoneDatabase::bundled(), TimeZoneDatabaseSource::Bundled);
+        }
+        for directory in zoneinfo_directories() {
+            if let Ok(database) = TimeZoneDatabase::from_dir(&directory) {
+                return (database, TimeZoneDatabaseSource::System(directory));
+            }
+        }
+        let bundled = TimeZoneDatabase::bundled();
+        if !bundled.is_definitively_empty() {
+            return (bundled, TimeZoneDatabaseSource::Bundled);
+        }
+        let database = tz::db().clone();
+        if database.is_definitively_empty() {
+            (database, TimeZoneDatabaseSource::Unavailable)
+        } else {
+            (database, TimeZoneDatabaseSource::Concatenated)
+        }
+    })
+}
+
+// TZDIR, then the directories jiff checks on unix
+fn zoneinfo_directories() -> Vec<PathBuf> {
+    let mut directories = vec![];
+    if let Some(tzdir) = std::env::var_os("TZDIR") {
+        directories.push(PathBuf::from(tzdir));
+    }
+    if cfg!(unix) {
+        directories.extend(
+            [
+                "/usr/share/zoneinfo",
+                "/usr/share/lib/zoneinfo",
+                "/etc/zoneinfo",
+            ]
+            .map(PathBuf::from),
+        );
+    }
+    directories
+}
+
+// Look up a time zone by IANA name, TZif file path or POSIX TZ string like
+// EST5EDT,M3.2.0,M11.1.0. Relative paths are resolved against cwd.
+pub fn resolve_time_zone(name: &str, cwd: &str) -> Result<TimeZone, String> {
+    if name.starts_with(['/', '.', '~']) {
+        return time_zone_from_tzif_file(&expand_path_with(name, cwd, true));
+    }
+    time_zone_database().get(name).or_else(|err| {
+        TimeZone::posix(name).or_else(|_| {
+            // a relative path like zones/Factory looks just like an IANA name
+            let path = expand_path_with(name, cwd, true);
+            if path.is_file() {
+                time_zone_from_tzif_file(&path)
+            } else {
+                Err(err.to_string())
+            }
+        })
+    })
+}
+
+// Zones loaded from a zoneinfo directory keep their IANA name, e.g.
+// /usr/share/zoneinfo/America/Chicago is America/Chicago. Other files get a name
+// no command could parse back, so they use the POSIX TZ string at the end of the
+// file instead and are written with just their offset like POSIX TZ strings.
+fn time_zone_from_tzif_file(path: &Path) -> Result<TimeZone, String> {
+    let data = std::fs::read(path)
+        .map_err(|err| format!("could not read TZif file {}: {err}", path.display()))?;
+    let path_name = path.to_string_lossy();
+    let name = match path_name.split_once("zoneinfo/") {
+        Some((_, name)) if !name.is_empty() => name,
+        _ => &path_name,
+    };
+    let tz = TimeZone::tzif(name, &data)
+        .map_err(|err| format!("{} is not a valid TZif file: {err}", path.display()))?;
+    if time_zone_database().get(name).is_ok() {
+        return Ok(tz);
+    }
+    match tzif_footer(&data).and_then(|footer| TimeZone::posix(footer).ok()) {
+        Some(posix) => Ok(posix),
+        // version 1 files have no footer, all that's left is the offset in use now
+        None => Ok(TimeZone::fixed(tz.to_offset(Timestamp::now()))),
+    }
+}
+
+// Version 2 and later TZif files end with a POSIX TZ string between newlines
+fn tzif_footer(data: &[u8]) -> Option<&str> {
+    let data = data.strip_suffix(b"\n")?;
+    let start = data.iter().rposition(|&byte| byte == b'\n')? + 1;
+    std::str::from_utf8(&data[start..])
+        .ok()
+        .filter(|footer| !footer.is_empty())
+}
+
+// The time zone local times are in: the session's TZ when it's set to a zone we
+// can load, otherwise the system time zone. Nushell only passes its environment to
+// the plugin when it starts, so commands that need it read TZ from the engine.
+pub fn local_time_zone(engine: &EngineInterface) -> TimeZone {
+    let tz = match engine.get_env_var("TZ") {
+        Ok(Some(value)) => value.coerce_into_string().unwrap_or_default(),
+        _ => String::new(),
+    };
+    time_zone_from_tz_variable(&tz, || engine.get_current_dir().unwrap_or_default())
+        .unwrap_or_else(TimeZone::system)
+}
+
+pub fn local_now(engine: &EngineInterface) -> Zoned {
+    Timestamp::now().to_zoned(local_time_zone(engine))
+}
+
+// Like libc, TZ may start with a colon and an unusable TZ falls back to the system zone
+fn time_zone_from_tz_variable(tz: &str, cwd: impl FnOnce() -> String) -> Option<TimeZone> {
+    let tz = tz.trim();
+    let tz = tz.strip_prefix(':').unwrap_or(tz);
+    if tz.is_empty() {
+        return None;
+    }
+    resolve_time_zone(tz, &cwd()).ok()
+}
+
+// The version line at the top of tzdata.zi, e.g. "# version 2025b"
+fn system_tzdb_version() -> Option<String> {
+    read_tzdata_source()?
+        .lines()
+        .find_map(|line| line.strip_prefix("# version "))
+        .map(|version| version.trim().to_string())
+}
+
+fn bundled_tzdb_version() -> Option<String> {
+    #[cfg(feature = "bundled-tzdb")]
+    {
+        jiff_tzdb::VERSION.map(|version| version.to_string())
+    }
+    #[cfg(not(feature = "bundled-tzdb"))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_zone_database_source() {
+        let (database, source) = time_zone_database_with_source();
+        assert!(database.get("America/Chicago").is_ok());
+        match source {
+            TimeZoneDatabaseSource::System(directory) => {
+                assert!(directory.join("America/Chicago").is_file());
+            }
+            source => assert_ne!(source.as_str(), "unavailable"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_time_zone() {
+        let tz = resolve_time_zone("EST5EDT,M3.2.0,M11.1.0", "/").unwrap();
+        let july = Timestamp::from_second(1_720_000_000).unwrap();
+        assert_eq!(tz.to_offset(july), tz::offset(-4));
+
+        if let Ok(tz) = resolve_time_zone("/usr/share/zoneinfo/America/Chicago", "/") {
+            assert_eq!(tz.iana_name(), Some("America/Chicago"));
+        }
+        if let Ok(tz) = resolve_time_zone("zoneinfo/Asia/Kolkata", "/usr/share") {
+            assert_eq!(
+                tz.to_offset(july),
+                tz::Offset::from_seconds(19_800).unwrap()
+            );
+        }
+
+        assert!(resolve_time_zone("./no-such-zone", "/").is_err());
+        assert!(resolve_time_zone("Not/AZone", "/").is_err());
+    }
+
+    #[test]
+    fn test_tzif_file_outside_zoneinfo_round_trips() -> Result<(), nu_protocol::ShellError> {
+        use nu_plugin_test_support::PluginTest;
+
+        let Ok(data) = std::fs::read("/usr/share/zoneinfo/Asia/Tokyo") else {
+            return Ok(());
+        };
+        let path = std::env::temp_dir().join(format!("dt-custom-{}.tzif", std::process::id()));
+        std::fs::write(&path, data).unwrap();
+
+        let tz = resolve_time_zone(&path.to_string_lossy(), "/").unwrap();
+        assert_eq!(tz.iana_name(), None);
+        let result = PluginTest::new("dt", DtPlugin.into())?.eval(&format!(
+            "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt to-timezone '{}' | dt add 1day",
+            path.display()
+        ));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            result?.into_value(nu_protocol::Span::test_data())?,
+            Value::test_string("2024-08-27T00:52:30+09:00[+09:00]")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_zone_from_tz_variable() {
+        let cwd = || "/".to_string();
+        assert_eq!(time_zone_from_tz_variable(":UTC", cwd), Some(TimeZone::UTC));
+        let tz = time_zone_from_tz_variable("EST5EDT,M3.2.0,M11.1.0", cwd).unwrap();
+        let july = Timestamp::from_second(1_720_000_000).unwrap();
+        assert_eq!(tz.to_offset(july), tz::offset(-4));
+        assert_eq!(time_zone_from_tz_variable("", cwd), None);
+        assert_eq!(time_zone_from_tz_variable("Not/AZone", cwd), None);
+    }
+
+    #[test]
+    fn test_local_time_zone_follows_session_tz() -> Result<(), nu_protocol::ShellError> {
+        use nu_plugin_test_support::PluginTest;
+
+        let result = PluginTest::new("dt", DtPlugin.into())?
+            .eval(
+                "$env.TZ = 'Asia/Kolkata'; '2024-08-25T12:00:00+00:00[UTC]' | dt to-timezone local",
+            )?
+            .into_value(nu_protocol::Span::test_data())?;
+        assert_eq!(
+            result,
+            Value::test_string("2024-08-25T17:30:00+05:30[Asia/Kolkata]")
+        );
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
+use super::fiscal::{FiscalCalendar, FiscalUnit, get_fiscal_part_from_zoned};
+use super::timezones::parse_utc_offset;
+use super::tzdb::{local_time_zone, resolve_time_zone, time_zone_database};
 use jiff::{
+    RoundMode,
     Span as JiffSpan,
     Timestamp,
     ToSpan,
     Unit,
     Zoned,
     civil,
+    civil::Weekday,
     //fmt::friendly::{Designator, Spacing, SpanPrinter},
     fmt::temporal::{DateTimeParser, Pieces},
     tz,
@@ -174,12 +179,14 @@ pub fn parse_datetime_string_into_pieces(
     // Ok(zdt)
 }
 
-// Parse a string into a jiff datetime and add nanoseconds to it optionally
+// Parse a string into a jiff datetime and add nanoseconds to it optionally. Strings
+// without an offset or time zone are in local_tz.
 pub fn parse_datetime_string_add_nanos_optionally(
     s: &str,
     duration_nanos: Option<i64>,
     span: NuSpan,
     jiff_span: Option<JiffSpan>,
+    local_tz: &TimeZone,
 ) -> Result<Zoned, LabeledError> {
     /*let date_time = parse_datetime(s).map_err(|err| {
         LabeledError::new(err.to_string()).with_label(
@@ -256,21 +263,21 @@ pub fn parse_datetime_string_add_nanos_optionally(
         if see_debug_values {
             eprintln!("civil Date (SHORT_DATE): {:?}", date);
         }
-        date.to_zoned(TimeZone::system())
+        date.to_zoned(local_tz.clone())
             .map_err(|err| LabeledError::new(err.to_string()))?
     } else if let Ok(date) = civil::Date::strptime(SHORT_DATE_USA_2YEAR, s) {
         if see_debug_values {
             eprintln!("civil Date USA 2yr (SHORT_DATE_USA_2YEAR): {:?}", date);
         }
-        date.to_zoned(TimeZone::system())
+        date.to_zoned(local_tz.clone())
             .map_err(|err| LabeledError::new(err.to_string()))?
     } else if let Ok(date) = civil::Date::strptime(SHORT_DATE_USA_4YEAR, s) {
         if see_debug_values {
             eprintln!("civil Date USA 4yr (SHORT_DATE_USA_4YEAR): {:?}", date);
         }
-        date.to_zoned(TimeZone::system())
+        date.to_zoned(local_tz.clone())
             .map_err(|err| LabeledError::new(err.to_string()))?
-    } else if let Ok(zdt) = PARSER.parse_zoned(s) {
+    } else if let Ok(zdt) = PARSER.parse_zoned_with(time_zone_database(), s) {
         if see_debug_values {
             eprintln!("Zoned: {:?}", zdt);
         }
@@ -309,25 +316,25 @@ pub fn parse_datetime_string_add_nanos_optionally(
         if see_debug_values {
             eprintln!("Timestamp: {:?}", ts);
         }
-        ts.to_zoned(TimeZone::system())
+        ts.to_zoned(local_tz.clone())
     } else if let Ok(dt) = PARSER.parse_datetime(s) {
         if see_debug_values {
             eprintln!("Datetime: {:?}", dt);
         }
-        dt.to_zoned(TimeZone::system())
+        dt.to_zoned(local_tz.clone())
             .map_err(|err| LabeledError::new(err.to_string()))?
     } else if let Ok(date) = PARSER.parse_date(s) {
         if see_debug_values {
             eprintln!("Date: {:?}", date);
         }
-        date.to_zoned(TimeZone::system())
+        date.to_zoned(local_tz.clone())
             .map_err(|err| LabeledError::new(err.to_string()))?
     } else if let Ok(time) = PARSER.parse_time(s) {
         if see_debug_values {
             eprintln!("Time: {:?}", time);
         }
-        time.to_datetime(Zoned::now().datetime().date())
-            .to_zoned(TimeZone::system())
+        time.to_datetime(Timestamp::now().to_zoned(local_tz.clone()).date())
+            .to_zoned(local_tz.clone())
             .map_err(|err| LabeledError::new(err.to_string()))?
         // } else if let Ok(span) = Span::parse(s) {
         //     return span.to_zoned(local_tz);
@@ -450,11 +457,98 @@ pub fn parse_datetime_string_add_nanos_optionally(
     }
 }
 
+// Parse a duration string like 1d, -2w, T1h or P1y2m into a jiff span. The P date
+// designator is inferred if it's missing because jiff's span parser requires it.
+// Strings that still aren't ISO 8601 durations, like 1h, 30min or 10s, are parsed
+// with jiff's friendly format, so 1m stays a month rather than a minute.
+pub fn parse_jiff_span_from_duration_string(
+    mut duration_string: Spanned<String>,
+) -> Result<JiffSpan, LabeledError> {
+    let original = duration_string.item.clone();
+    // The jiff span is roughly comparable to this
+    // P(\d+y)?(\d+m)?(\d+w)?(\d+d)?(T(\d+h)?(\d+m)?(\d+s)?)?
+    // P = date designator
+    // y = years
+    // m = months
+    // w = weeks
+    // d = days
+    // T = time designator
+    // h = hours
+    // m = minutes
+    // s = seconds
+    // .000 = milliseconds
+    // .000000 = microseconds
+    // .000000000 = nanoseconds
+
+    // is it negative
+    if (duration_string.item.starts_with('-')
+        || duration_string.item.starts_with('+'))
+        // is it longer than 2
+        && duration_string.item.len() > 2
+        // is the 2nd character not a P
+        && duration_string.item.chars().nth(1).unwrap() != 'P'
+    {
+        duration_string.item.insert(1, 'P');
+    } else if !duration_string.item.starts_with('P') {
+        // else if it doesn't start with P, add it because jiff's span parser requires it
+        duration_string.item.insert(0, 'P');
+    }
+
+    duration_string
+        .item
+        .parse()
+        .or_else(|err: jiff::Error| {
+            jiff::fmt::friendly::SpanParser::new()
+                .parse_span(original.trim())
+                .map_err(|_| err)
+        })
+        .map_err(|err| {
+            LabeledError::new(format!("Error parsing duration: {err}"))
+                .with_label(
+                    format!("error parsing {:?} as a jiff span", duration_string.item),
+                    duration_string.span,
+                )
+                .with_help(
+                    r#"These are the valid component abbreviations:
++ = positive
+- = negative
+P = date designator (inferred if you forget it)
+y = years
+m = months
+w = weeks
+d = days
+T = time designator
+h = hours
+m = minutes
+s = seconds
+.000 = milliseconds
+.000000 = microseconds
+.000000000 = nanoseconds
+
+Time units can also be given without T, like 1h, 30min or 10s, but m alone is months.
+"#,
+                )
+        })
+}
+
 pub fn get_part_from_zoned_as_i16(
     part_string: String,
     datetime: Zoned,
+    fiscal: &FiscalCalendar,
 ) -> Result<i16, LabeledError> {
     let date = match part_string.as_ref() {
+        "fiscal_year" | "fiscalyear" | "fy" => {
+            get_fiscal_part_from_zoned(FiscalUnit::Year, &datetime, fiscal)?
+        }
+        "fiscal_quarter" | "fiscalquarter" | "fq" | "fqtr" => {
+            get_fiscal_part_from_zoned(FiscalUnit::Quarter, &datetime, fiscal)?
+        }
+        "fiscal_period" | "fiscalperiod" | "fp" | "fiscal_month" => {
+            get_fiscal_part_from_zoned(FiscalUnit::Period, &datetime, fiscal)?
+        }
+        "fiscal_week" | "fiscalweek" | "fw" | "fwk" => {
+            get_fiscal_part_from_zoned(FiscalUnit::Week, &datetime, fiscal)?
+        }
         "year" | "years" | "yyyy" | "yy" | "yr" | "yrs" => datetime.year(),
         "quarter" | "qq" | "q" | "qs" | "qtr" => match datetime.month().into() {
             1..=3 => 1,
@@ -492,7 +586,7 @@ pub fn get_part_from_zoned_as_i16(
 }
 
 pub fn get_unit_from_unit_string(unit_name: String) -> Result<Unit, LabeledError> {
-    let unit = match unit_name.as_ref() {
+    match unit_name.as_ref() {
         "year" | "years" | "yyyy" | "yy" | "yr" | "yrs" => Ok(Unit::Year),
         "month" | "months" | "mth" | "mths" | "mm" | "m" | "mon" => Ok(Unit::Month),
         "day" | "days" | "dd" | "d" => Ok(Unit::Day),
@@ -503,14 +597,213 @@ pub fn get_unit_from_unit_string(unit_name: String) -> Result<Unit, LabeledError
         "millisecond" | "ms" | "millis" => Ok(Unit::Millisecond),
         "microsecond" | "mcs" | "us" | "micros" => Ok(Unit::Microsecond),
         "nanosecond" | "ns" | "nano" | "nanos" => Ok(Unit::Nanosecond),
-        _ => {
-            return Err(LabeledError::new(
-                "please supply a valid unit name to extract from a date/datetime. see dt part --list for list of abbreviations.",
-            ));
+        _ => Err(LabeledError::new(
+            "please supply a valid unit name to extract from a date/datetime. see dt part --list for list of abbreviations.",
+        )),
+    }
+}
+
+// jiff has no quarter unit, so commands that work with calendar periods check
+// for the quarter abbreviations before falling back to get_unit_from_unit_string
+pub fn is_quarter_unit_string(unit_name: &str) -> bool {
+    matches!(unit_name, "quarter" | "qq" | "q" | "qs" | "qtr")
+}
+
+pub fn get_weekday_from_string(weekday: Spanned<String>) -> Result<Weekday, LabeledError> {
+    match weekday.item.to_lowercase().as_ref() {
+        "monday" | "mon" | "mo" => Ok(Weekday::Monday),
+        "tuesday" | "tue" | "tu" => Ok(Weekday::Tuesday),
+        "wednesday" | "wed" | "we" => Ok(Weekday::Wednesday),
+        "thursday" | "thu" | "th" => Ok(Weekday::Thursday),
+        "friday" | "fri" | "fr" => Ok(Weekday::Friday),
+        "saturday" | "sat" | "sa" => Ok(Weekday::Saturday),
+        "sunday" | "sun" | "su" => Ok(Weekday::Sunday),
+        _ => Err(
+            LabeledError::new(format!("Invalid weekday: {}", weekday.item)).with_label(
+                "expected a weekday name like monday, mon or mo",
+                weekday.span,
+            ),
+        ),
+    }
+}
+
+pub fn get_round_mode_from_string(mode: Spanned<String>) -> Result<RoundMode, LabeledError> {
+    match mode.item.as_ref() {
+        "ceil" => Ok(RoundMode::Ceil),
+        "floor" => Ok(RoundMode::Floor),
+        "expand" => Ok(RoundMode::Expand),
+        "trunc" => Ok(RoundMode::Trunc),
+        "half-ceil" => Ok(RoundMode::HalfCeil),
+        "half-floor" => Ok(RoundMode::HalfFloor),
+        "half-expand" => Ok(RoundMode::HalfExpand),
+        "half-trunc" => Ok(RoundMode::HalfTrunc),
+        "half-even" => Ok(RoundMode::HalfEven),
+        _ => Err(
+            LabeledError::new(format!("Invalid rounding mode: {}", mode.item)).with_label(
+                "expected one of ceil, floor, expand, trunc, half-ceil, half-floor, half-expand, half-trunc or half-even",
+                mode.span,
+            ),
+        ),
+    }
+}
+
+// Look up a time zone by IANA name, UTC or local for the session's time zone, a fixed
+// offset like +05:30, a TZif file path or a POSIX TZ string like EST5EDT,M3.2.0,M11.1.0
+pub fn get_time_zone_from_string(
+    engine: &EngineInterface,
+    time_zone: Spanned<String>,
+) -> Result<TimeZone, LabeledError> {
+    let unknown_time_zone = |message: String| {
+        LabeledError::new(format!("Unknown time zone: {}", time_zone.item))
+            .with_label(message, time_zone.span)
+            .with_help("use an IANA time zone name like America/Chicago, UTC, local, a fixed offset like +05:30, a TZif file path or a POSIX TZ string like EST5EDT,M3.2.0,M11.1.0")
+    };
+    match time_zone.item.as_ref() {
+        "local" | "LOCAL" | "Local" => Ok(local_time_zone(engine)),
+        "utc" | "UTC" | "Utc" | "Z" | "z" => Ok(TimeZone::UTC),
+        name if name.starts_with(['+', '-']) => parse_utc_offset(name)
+            .map(TimeZone::fixed)
+            .ok_or_else(|| unknown_time_zone("invalid fixed offset".to_string())),
+        name => resolve_time_zone(name, &engine.get_current_dir().unwrap_or_default())
+            .map_err(unknown_time_zone),
+    }
+}
+
+// Read a list of strings like favorite_zones from the plugin's config in
+// $env.config.plugins.dt, or None when it isn't set
+pub fn get_plugin_config_strings(
+    engine: &EngineInterface,
+    key: &str,
+) -> Result<Option<Vec<Spanned<String>>>, LabeledError> {
+    let Some(config) = engine.get_plugin_config()? else {
+        return Ok(None);
+    };
+    let Some(value) = config.as_record().ok().and_then(|config| config.get(key)) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        LabeledError::new(format!("Invalid plugin config {key}"))
+            .with_label("expected a list of strings", value.span())
+    };
+    value
+        .as_list()
+        .map_err(|_| invalid())?
+        .iter()
+        .map(|item| {
+            item.as_str()
+                .map(|text| text.to_string().into_spanned(item.span()))
+                .map_err(|_| invalid())
+        })
+        .collect::<Result<Vec<_>, LabeledError>>()
+        .map(Some)
+}
+
+// Convert a piped in or parameter date/datetime value into a jiff::Zoned
+pub fn get_zoned_from_value(value: &Value, local_tz: &TimeZone) -> Result<Zoned, LabeledError> {
+    let span = value.span();
+    match value {
+        // so much easier just to output chrono as rfc 3339 and let jiff parse it
+        Value::Date { val, .. } => parse_datetime_string_add_nanos_optionally(
+            &val.to_rfc3339(),
+            None,
+            span,
+            None,
+            local_tz,
+        ),
+        Value::String { val, .. } => {
+            parse_datetime_string_add_nanos_optionally(val, None, span, None, local_tz)
+        }
+        _ => Err(LabeledError::new("Expected a date or datetime".to_string())
+            .with_label(format!("got {}", value.get_type()), span)),
+    }
+}
+
+// What to do when month or year arithmetic lands on a day that doesn't exist
+// in the target month, e.g. 2024-01-31 + 1 month
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonthOverflow {
+    // Clamp to the last day of the target month (jiff's default)
+    Clamp,
+    // Roll the extra days over into the following month
+    Roll,
+    // Return an error
+    Reject,
+}
+
+pub fn get_month_overflow_from_string(
+    overflow: Option<Spanned<String>>,
+) -> Result<MonthOverflow, LabeledError> {
+    let Some(overflow) = overflow else {
+        return Ok(MonthOverflow::Clamp);
+    };
+    match overflow.item.as_ref() {
+        "clamp" => Ok(MonthOverflow::Clamp),
+        "roll" => Ok(MonthOverflow::Roll),
+        "reject" => Ok(MonthOverflow::Reject),
+        _ => Err(
+            LabeledError::new(format!("Invalid overflow option: {}", overflow.item))
+                .with_label("expected one of clamp, roll or reject", overflow.span),
+        ),
+    }
+}
+
+// Add a jiff span to a zoned datetime, handling the years and months of the span
+// with the provided overflow policy. When preserve_end_of_month is set, a datetime
+// on the last day of its month stays on the last day of the target month.
+pub fn add_span_with_overflow(
+    datetime: &Zoned,
+    jiff_span: JiffSpan,
+    overflow: MonthOverflow,
+    preserve_end_of_month: bool,
+) -> Result<Zoned, LabeledError> {
+    let total_months = i64::from(jiff_span.get_years()) * 12 + i64::from(jiff_span.get_months());
+    if total_months == 0 || (overflow == MonthOverflow::Clamp && !preserve_end_of_month) {
+        return datetime
+            .checked_add(jiff_span)
+            .map_err(|err| LabeledError::new(err.to_string()));
+    }
+
+    let date = datetime.date();
+    let target_first = date
+        .first_of_month()
+        .checked_add(total_months.months())
+        .map_err(|err| LabeledError::new(err.to_string()))?;
+    let target_last_day = target_first.days_in_month();
+
+    let target_date = if preserve_end_of_month && date == date.last_of_month() {
+        target_first.last_of_month()
+    } else if date.day() <= target_last_day {
+        target_first
+            .with()
+            .day(date.day())
+            .build()
+            .map_err(|err| LabeledError::new(err.to_string()))?
+    } else {
+        match overflow {
+            MonthOverflow::Clamp => target_first.last_of_month(),
+            MonthOverflow::Roll => target_first
+                .last_of_month()
+                .checked_add(i64::from(date.day() - target_last_day).days())
+                .map_err(|err| LabeledError::new(err.to_string()))?,
+            MonthOverflow::Reject => {
+                return Err(LabeledError::new(format!(
+                    "Adding {jiff_span} to {date} overflows the end of {}-{:02}",
+                    target_first.year(),
+                    target_first.month()
+                ))
+                .with_help("use --overflow clamp or --overflow roll to allow this"));
+            }
         }
     };
 
-    unit
+    // Add the remaining non-calendar units the same way jiff would
+    let remaining_span = jiff_span.years(0).months(0);
+    target_date
+        .to_datetime(datetime.time())
+        .to_zoned(datetime.time_zone().clone())
+        .map_err(|err| LabeledError::new(err.to_string()))?
+        .checked_add(remaining_span)
+        .map_err(|err| LabeledError::new(err.to_string()))
 }
 
 #[allow(dead_code)]
@@ -645,6 +938,38 @@ pub fn get_unit_abbreviations() -> Vec<Value> {
         NuSpan::unknown(),
     );
     records.push(rec);
+    let rec = Value::record(
+        record! {
+        "name" => Value::test_string("fiscal_year"),
+        "abbreviations" => Value::test_string("fiscal_year, fiscalyear, fy"),
+        },
+        NuSpan::unknown(),
+    );
+    records.push(rec);
+    let rec = Value::record(
+        record! {
+        "name" => Value::test_string("fiscal_quarter"),
+        "abbreviations" => Value::test_string("fiscal_quarter, fiscalquarter, fq, fqtr"),
+        },
+        NuSpan::unknown(),
+    );
+    records.push(rec);
+    let rec = Value::record(
+        record! {
+        "name" => Value::test_string("fiscal_period"),
+        "abbreviations" => Value::test_string("fiscal_period, fiscalperiod, fp, fiscal_month"),
+        },
+        NuSpan::unknown(),
+    );
+    records.push(rec);
+    let rec = Value::record(
+        record! {
+        "name" => Value::test_string("fiscal_week"),
+        "abbreviations" => Value::test_string("fiscal_week, fiscalweek, fw, fwk"),
+        },
+        NuSpan::unknown(),
+    );
+    records.push(rec);
 
     records
 }
@@ -751,10 +1076,11 @@ mod tests {
         let datetime = Zoned::now();
         let part_string = "year".to_string();
 
-        let result = get_part_from_zoned_as_i16(part_string, datetime.clone());
+        let result =
+            get_part_from_zoned_as_i16(part_string, datetime.clone(), &FiscalCalendar::default());
         assert!(result.is_ok());
         let year = result.unwrap();
-        assert_eq!(year, datetime.year() as i16);
+        assert_eq!(year, datetime.year());
     }
 
     #[test]
@@ -767,6 +1093,23 @@ mod tests {
         assert_eq!(unit, Unit::Year);
     }
 
+    #[test]
+    fn test_parse_jiff_span_from_duration_string() {
+        let parse = |duration: &str| {
+            parse_jiff_span_from_duration_string(Spanned {
+                item: duration.to_string(),
+                span: NuSpan::unknown(),
+            })
+        };
+        assert_eq!(parse("1h").unwrap().fieldwise(), 1.hour());
+        assert_eq!(parse("30min").unwrap().fieldwise(), 30.minutes());
+        assert_eq!(parse("10s").unwrap().fieldwise(), 10.seconds());
+        assert_eq!(parse("T1h").unwrap().fieldwise(), 1.hour());
+        assert_eq!(parse("1m").unwrap().fieldwise(), 1.month());
+        assert_eq!(parse("-2w").unwrap().fieldwise(), (-2).weeks());
+        assert!(parse("soon").is_err());
+    }
+
     #[test]
     fn test_parse_number_and_unit_string() {
         let input_value = Spanned {
@@ -784,7 +1127,7 @@ mod tests {
     #[test]
     fn test_get_unit_abbreviations() {
         let abbreviations = get_unit_abbreviations();
-        assert_eq!(abbreviations.len(), 13);
+        assert_eq!(abbreviations.len(), 17);
     }
 
     #[test]
@@ -807,6 +1150,49 @@ mod tests {
         assert_eq!(result, "1yrs");
     }
 
+    #[test]
+    fn test_add_span_with_overflow() {
+        let datetime: Zoned = "2023-01-31T09:30:00-06:00[America/Chicago]"
+            .parse()
+            .unwrap();
+        let span = jiff::Span::new().months(1);
+
+        let clamped = add_span_with_overflow(&datetime, span, MonthOverflow::Clamp, false).unwrap();
+        assert_eq!(clamped.date(), civil::date(2023, 2, 28));
+        assert_eq!(clamped.time(), civil::time(9, 30, 0, 0));
+
+        let rolled = add_span_with_overflow(&datetime, span, MonthOverflow::Roll, false).unwrap();
+        assert_eq!(rolled.date(), civil::date(2023, 3, 3));
+
+        let rejected = add_span_with_overflow(&datetime, span, MonthOverflow::Reject, false);
+        assert!(rejected.is_err());
+    }
+
+    #[test]
+    fn test_add_span_with_overflow_preserve_end_of_month() {
+        let datetime: Zoned = "2024-02-29T00:00:00-06:00[America/Chicago]"
+            .parse()
+            .unwrap();
+
+        let next_year = add_span_with_overflow(
+            &datetime,
+            jiff::Span::new().years(1),
+            MonthOverflow::Reject,
+            true,
+        )
+        .unwrap();
+        assert_eq!(next_year.date(), civil::date(2025, 2, 28));
+
+        let next_month = add_span_with_overflow(
+            &datetime,
+            jiff::Span::new().months(1).days(1),
+            MonthOverflow::Clamp,
+            true,
+        )
+        .unwrap();
+        assert_eq!(next_month.date(), civil::date(2024, 4, 1));
+    }
+
     #[test]
     fn test_strptime_relaxed() {
         let fmt = "%Y-%m-%dT%H:%M:%S%:z";
@@ -826,7 +1212,13 @@ mod tests {
         let duration_nanos = Some(1_000_000_000); // 1 second
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.second(), 1);
@@ -838,7 +1230,13 @@ mod tests {
         let duration_nanos = Some(60_000_000_000); // 1 minute
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.minute(), 1);
@@ -850,7 +1248,13 @@ mod tests {
         let duration_nanos = Some(3_600_000_000_000); // 1 hour
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.hour(), 1);
@@ -862,7 +1266,13 @@ mod tests {
         let duration_nanos = Some(86_400_000_000_000); // 1 day
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.day(), 2);
@@ -874,7 +1284,13 @@ mod tests {
         let duration_nanos = None;
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.second(), 0);
@@ -886,7 +1302,13 @@ mod tests {
         let duration_nanos = None;
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.second(), 0);
@@ -898,7 +1320,13 @@ mod tests {
         let duration_nanos = Some(86_400_000_000_000); // 1 day
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.day(), 2);
@@ -910,7 +1338,13 @@ mod tests {
         let duration_nanos = None;
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.hour(), 0);
@@ -927,7 +1361,13 @@ mod tests {
         let duration_nanos = Some(1_000_000_000); // 1 second
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
 
@@ -941,7 +1381,13 @@ mod tests {
         let duration_nanos = None;
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.second(), 6);
@@ -953,7 +1399,13 @@ mod tests {
         let duration_nanos = Some(1_000_000_000); // 1 second
         let span = NuSpan::unknown();
 
-        let result = parse_datetime_string_add_nanos_optionally(s, duration_nanos, span, None);
+        let result = parse_datetime_string_add_nanos_optionally(
+            s,
+            duration_nanos,
+            span,
+            None,
+            &TimeZone::system(),
+        );
         assert!(result.is_ok());
         let datetime = result.unwrap();
         assert_eq!(datetime.second(), 7);
@@ -0,0 +1,348 @@
+use super::business_days::with_date;
+use super::tzdb::{local_now, local_time_zone};
+use super::utils::{get_weekday_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{civil, civil::Weekday};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+
+pub struct DtNthWeekday;
+
+impl SimplePluginCommand for DtNthWeekday {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt nth-weekday"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .required(
+                "n",
+                SyntaxShape::Int,
+                "Which occurrence in the month, 1 to 5 from the start or -1 to -5 from the end.",
+            )
+            .required(
+                "weekday",
+                SyntaxShape::String,
+                "Weekday to find, e.g. monday, tue or fr.",
+            )
+            .named(
+                "year",
+                SyntaxShape::Int,
+                "Year to look in instead of the provided datetime's year.",
+                Some('y'),
+            )
+            .named(
+                "month",
+                SyntaxShape::Int,
+                "Month number (1-12) to look in instead of the provided datetime's month.",
+                Some('m'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return the nth occurrence of a weekday in the month of the provided date or datetime, keeping its time of day and time zone, or in a given month and year"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "date",
+            "weekday",
+            "month",
+            "patch tuesday",
+            "last",
+            "occurrence",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt nth-weekday 2 tue",
+                description: "Return the second Tuesday of the month",
+                result: Some(Value::test_string(
+                    "2024-08-13T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt nth-weekday -1 friday",
+                description: "Return the last Friday of the month",
+                result: Some(Value::test_string(
+                    "2024-08-30T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt nth-weekday 4 thu --month 11",
+                description: "Return the fourth Thursday of November in the same year",
+                result: Some(Value::test_string(
+                    "2024-11-28T10:52:30-06:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "dt nth-weekday 2 tue --year 2024 --month 8",
+                description: "Return the start of the second Tuesday of a month without a piped in date",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let nth: Spanned<i64> = call.req(0)?;
+        let weekday = get_weekday_from_string(call.req(1)?)?;
+        let year: Option<Spanned<i64>> = call.get_flag("year")?;
+        let month: Option<Spanned<i64>> = call.get_flag("month")?;
+
+        if nth.item == 0 || !(-5..=5).contains(&nth.item) {
+            return Err(LabeledError::new("n must be from 1 to 5 or from -1 to -5")
+                .with_label(format!("got {}", nth.item), nth.span));
+        }
+        // Without an input date the missing parts come from today, starting at midnight
+        let datetime = match input {
+            Value::Nothing { .. } if year.is_some() || month.is_some() => local_now(engine)
+                .start_of_day()
+                .map_err(|err| LabeledError::new(err.to_string()))?,
+            _ => get_zoned_from_value(input, &local_time_zone(engine))?,
+        };
+        let year = match year {
+            Some(year) => i16::try_from(year.item)
+                .ok()
+                .filter(|year| (-9999..=9999).contains(year))
+                .ok_or_else(|| {
+                    LabeledError::new("year must be between -9999 and 9999")
+                        .with_label(format!("got {}", year.item), year.span)
+                })?,
+            None => datetime.year(),
+        };
+        let month = match month {
+            Some(month) if !(1..=12).contains(&month.item) => {
+                return Err(
+                    LabeledError::new("month must be a month number from 1 to 12")
+                        .with_label(format!("got {}", month.item), month.span),
+                );
+            }
+            Some(month) => month.item as i8,
+            None => datetime.month(),
+        };
+
+        let date = civil::Date::new(year, month, 1)
+            .and_then(|first| first.nth_weekday_of_month(nth.item as i8, weekday))
+            .map_err(|_| {
+                LabeledError::new(format!(
+                    "There's no occurrence {} of {weekday:?} in {year:04}-{month:02}",
+                    nth.item
+                ))
+                .with_label("not in this month", nth.span)
+            })?;
+        let result = with_date(&datetime, date)?;
+        Ok(Value::string(result.to_string(), call.head))
+    }
+}
+
+pub struct DtNext;
+
+impl SimplePluginCommand for DtNext {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt next"
+    }
+
+    fn signature(&self) -> Signature {
+        relative_weekday_signature(self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Return the next occurrence of a weekday after the provided date or datetime, keeping its time of day and time zone"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "weekday", "following", "upcoming", "coming"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt next monday",
+                description: "Return the next Monday after a Sunday",
+                result: Some(Value::test_string(
+                    "2024-08-26T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt next sun",
+                description: "Return the next Sunday, a week later when the date is already a Sunday",
+                result: Some(Value::test_string(
+                    "2024-09-01T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt next sun --inclusive",
+                description: "Return the date itself when it's already a Sunday",
+                result: Some(Value::test_string(
+                    "2024-08-25T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        relative_weekday_from_call(engine, call, input, 1)
+    }
+}
+
+pub struct DtPrev;
+
+impl SimplePluginCommand for DtPrev {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt prev"
+    }
+
+    fn signature(&self) -> Signature {
+        relative_weekday_signature(self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Return the previous occurrence of a weekday before the provided date or datetime, keeping its time of day and time zone"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "weekday", "previous", "last", "preceding"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt prev fri",
+                description: "Return the previous Friday",
+                result: Some(Value::test_string(
+                    "2024-08-23T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-25T10:52:30-05:00[America/Chicago]' | dt prev sunday --inclusive",
+                description: "Return the date itself when it's already a Sunday",
+                result: Some(Value::test_string(
+                    "2024-08-25T10:52:30-05:00[America/Chicago]",
+                )),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        relative_weekday_from_call(engine, call, input, -1)
+    }
+}
+
+fn relative_weekday_signature(name: &str) -> Signature {
+    Signature::build(name)
+        .required(
+            "weekday",
+            SyntaxShape::String,
+            "Weekday to find, e.g. monday, tue or fr.",
+        )
+        .switch(
+            "inclusive",
+            "Return the provided date itself when it's already on the weekday",
+            Some('i'),
+        )
+        .category(Category::Date)
+}
+
+// Find the weekday after (direction 1) or before (direction -1) the input's date
+fn relative_weekday_from_call(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+    input: &Value,
+    direction: i32,
+) -> Result<Value, LabeledError> {
+    let weekday = get_weekday_from_string(call.req(0)?)?;
+    let inclusive = call.has_flag("inclusive")?;
+
+    let datetime = get_zoned_from_value(input, &local_time_zone(engine))?;
+    let date = relative_weekday(datetime.date(), weekday, direction, inclusive)?;
+    let result = with_date(&datetime, date)?;
+    Ok(Value::string(result.to_string(), call.head))
+}
+
+pub fn relative_weekday(
+    date: civil::Date,
+    weekday: Weekday,
+    direction: i32,
+    inclusive: bool,
+) -> Result<civil::Date, LabeledError> {
+    if inclusive && date.weekday() == weekday {
+        return Ok(date);
+    }
+    date.nth_weekday(direction, weekday)
+        .map_err(|err| LabeledError::new(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtNthWeekday)?;
+        plugin_test.test_command_examples(&DtNext)?;
+        plugin_test.test_command_examples(&DtPrev)
+    }
+
+    #[test]
+    fn test_nth_weekday_without_input() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        let result = plugin_test
+            .eval("dt nth-weekday 2 tue --year 2024 --month 8")?
+            .into_value(nu_protocol::Span::test_data())?;
+        assert!(result.as_str()?.starts_with("2024-08-13T00:00:00"));
+        assert!(plugin_test.eval("dt nth-weekday 2 tue").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_weekday() {
+        // 2024-08-28 is a Wednesday
+        let wednesday = date(2024, 8, 28);
+        assert_eq!(
+            relative_weekday(wednesday, Weekday::Wednesday, 1, false).unwrap(),
+            date(2024, 9, 4)
+        );
+        assert_eq!(
+            relative_weekday(wednesday, Weekday::Wednesday, -1, false).unwrap(),
+            date(2024, 8, 21)
+        );
+        assert_eq!(
+            relative_weekday(wednesday, Weekday::Wednesday, -1, true).unwrap(),
+            wednesday
+        );
+        assert_eq!(
+            relative_weekday(wednesday, Weekday::Monday, -1, true).unwrap(),
+            date(2024, 8, 26)
+        );
+    }
+}
@@ -0,0 +1,441 @@
+use super::business_days::{
+    BusinessCalendar, business_calendar_flags, get_business_calendar_from_call,
+};
+use super::tzdb::local_time_zone;
+use super::utils::{
+    create_nushelly_duration_string, get_time_zone_from_string, get_zoned_from_value,
+    parse_jiff_span_from_duration_string,
+};
+use crate::DtPlugin;
+use jiff::{SignedDuration, Span as JiffSpan, SpanRound, ToSpan, Unit, Zoned, civil, tz::TimeZone};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+
+pub struct DtWorkTime;
+
+impl SimplePluginCommand for DtWorkTime {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt work-time"
+    }
+
+    fn signature(&self) -> Signature {
+        work_time_flags(
+            Signature::build(self.name())
+                .required(
+                    "start",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date or datetime to start counting working time from.",
+                )
+                .required(
+                    "end",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date or datetime to stop counting working time at.",
+                ),
+        )
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return the working time between two datetimes, only counting time within working hours on business days"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "business", "hours", "sla", "elapsed"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt work-time '2024-08-23T16:00:00-05:00[America/Chicago]' '2024-08-26T10:30:00-05:00[America/Chicago]'",
+                description: "Return the working time from a Friday afternoon to Monday morning with the default 09:00-17:00 hours",
+                result: Some(Value::test_string("PT2H30M\n2hrs 30mins")),
+            },
+            Example {
+                example: "dt work-time '2024-08-23T14:00:00+00:00[UTC]' '2024-08-26T14:00:00+00:00[UTC]' --hours 09:00-17:30 --timezone Europe/Berlin",
+                description: "Return the working time using Berlin working hours",
+                result: Some(Value::test_string("PT8H30M\n8hrs 30mins")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let start_value: Value = call.req(0)?;
+        let end_value: Value = call.req(1)?;
+        let (hours, calendar, time_zone) = get_work_time_options(engine, call)?;
+
+        let start = get_zoned_from_value(&start_value, &local_tz)?;
+        let end = get_zoned_from_value(&end_value, &local_tz)?;
+        let time_zone = time_zone.unwrap_or_else(|| start.time_zone().clone());
+        let worked = hours.work_time_between(
+            &calendar,
+            &start.with_time_zone(time_zone.clone()),
+            &end.with_time_zone(time_zone),
+        )?;
+
+        let span = JiffSpan::try_from(worked)
+            .and_then(|span| span.round(SpanRound::new().largest(Unit::Hour)))
+            .map_err(|err| LabeledError::new(err.to_string()))?;
+        let nushelly = create_nushelly_duration_string(span.abs());
+        let nushelly = if span.is_negative() {
+            format!("-{nushelly}")
+        } else {
+            nushelly
+        };
+        Ok(Value::string(format!("{span}\n{nushelly}"), call.head))
+    }
+}
+
+pub struct DtAddWorkTime;
+
+impl SimplePluginCommand for DtAddWorkTime {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt add-work-time"
+    }
+
+    fn signature(&self) -> Signature {
+        work_time_flags(Signature::build(self.name()).required(
+            "duration",
+            SyntaxShape::String,
+            "Working time to add like T4h or T1h30m, negative durations go backwards.",
+        ))
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Add working time to the provided datetime, only counting time within working hours on business days"
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec!["date", "time", "business", "hours", "sla", "deadline"]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-23T15:00:00-05:00[America/Chicago]' | dt add-work-time T4h",
+                description: "Push a Friday afternoon deadline 4 working hours forward into Monday",
+                result: Some(Value::test_string(
+                    "2024-08-26T11:00:00-05:00[America/Chicago]",
+                )),
+            },
+            Example {
+                example: "'2024-08-23T20:00:00+00:00[UTC]' | dt add-work-time T1h --hours 08:00-16:00 --timezone America/New_York",
+                description: "Add 1 working hour using New York working hours, keeping the datetime's own time zone",
+                result: Some(Value::test_string("2024-08-26T13:00:00+00:00[UTC]")),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let duration_string: Spanned<String> = call.req(0)?;
+        let (hours, calendar, time_zone) = get_work_time_options(engine, call)?;
+
+        let duration_span = duration_string.span;
+        let jiff_span = parse_jiff_span_from_duration_string(duration_string)?;
+        let duration = SignedDuration::try_from(jiff_span).map_err(|err| {
+            LabeledError::new(format!(
+                "Working time must be in hours or smaller units: {err}"
+            ))
+            .with_label("duration has calendar units", duration_span)
+        })?;
+
+        let datetime = get_zoned_from_value(input, &local_tz)?;
+        let time_zone = time_zone.unwrap_or_else(|| datetime.time_zone().clone());
+        let result =
+            hours.add_work_time(&calendar, &datetime.with_time_zone(time_zone), duration)?;
+        Ok(Value::string(
+            result
+                .with_time_zone(datetime.time_zone().clone())
+                .to_string(),
+            call.head,
+        ))
+    }
+}
+
+fn work_time_flags(signature: Signature) -> Signature {
+    business_calendar_flags(signature)
+        .named(
+            "hours",
+            SyntaxShape::String,
+            "Working hours on business days like 09:00-17:30 (default 09:00-17:00).",
+            Some('r'),
+        )
+        .named(
+            "timezone",
+            SyntaxShape::String,
+            "IANA time zone the working hours are in (defaults to the datetime's zone).",
+            Some('z'),
+        )
+}
+
+fn get_work_time_options(
+    engine: &EngineInterface,
+    call: &EvaluatedCall,
+) -> Result<(WorkingHours, BusinessCalendar, Option<TimeZone>), LabeledError> {
+    let hours = match call.get_flag("hours")? {
+        Some(hours) => get_working_hours_from_string(hours)?,
+        None => WorkingHours::default(),
+    };
+    let calendar = get_business_calendar_from_call(engine, call)?;
+    let time_zone = call
+        .get_flag("timezone")?
+        .map(|time_zone| get_time_zone_from_string(engine, time_zone))
+        .transpose()?;
+    Ok((hours, calendar, time_zone))
+}
+
+// The part of a business day that's worked, in the local time of a time zone
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub open: civil::Time,
+    pub close: civil::Time,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        WorkingHours {
+            open: civil::time(9, 0, 0, 0),
+            close: civil::time(17, 0, 0, 0),
+        }
+    }
+}
+
+impl WorkingHours {
+    // The instants working hours start and end on a date, or None when it isn't a
+    // business day. Times that don't exist because of DST resolve like jiff's
+    // compatible disambiguation.
+    pub fn window_on(
+        &self,
+        calendar: &BusinessCalendar,
+        date: civil::Date,
+        time_zone: &TimeZone,
+    ) -> Result<Option<(Zoned, Zoned)>, LabeledError> {
+        if !calendar.is_business_day(date) {
+            return Ok(None);
+        }
+        let to_zoned = |time: civil::Time| {
+            date.to_datetime(time)
+                .to_zoned(time_zone.clone())
+                .map_err(|err| LabeledError::new(err.to_string()))
+        };
+        Ok(Some((to_zoned(self.open)?, to_zoned(self.close)?)))
+    }
+
+    // Whether a datetime is within working hours in its own time zone
+    pub fn contains(
+        &self,
+        calendar: &BusinessCalendar,
+        datetime: &Zoned,
+    ) -> Result<bool, LabeledError> {
+        let window = self.window_on(calendar, datetime.date(), datetime.time_zone())?;
+        Ok(window.is_some_and(|(open, close)| open <= *datetime && *datetime < close))
+    }
+
+    // Elapsed working time between two datetimes in the same time zone, negative
+    // when end is before start
+    pub fn work_time_between(
+        &self,
+        calendar: &BusinessCalendar,
+        start: &Zoned,
+        end: &Zoned,
+    ) -> Result<SignedDuration, LabeledError> {
+        if end < start {
+            return Ok(-self.work_time_between(calendar, end, start)?);
+        }
+        let mut total = SignedDuration::ZERO;
+        for date in start.date().series(1.day()) {
+            if date > end.date() {
+                break;
+            }
+            let Some((open, close)) = self.window_on(calendar, date, start.time_zone())? else {
+                continue;
+            };
+            let from = open.max(start.clone());
+            let to = close.min(end.clone());
+            if from < to {
+                total += from.duration_until(&to);
+            }
+        }
+        Ok(total)
+    }
+
+    // Move a datetime by an amount of working time. Adding time that ends exactly
+    // at closing returns the closing time rather than the next opening time.
+    pub fn add_work_time(
+        &self,
+        calendar: &BusinessCalendar,
+        start: &Zoned,
+        duration: SignedDuration,
+    ) -> Result<Zoned, LabeledError> {
+        let to_error = |err: jiff::Error| LabeledError::new(err.to_string());
+        let forwards = !duration.is_negative();
+        let mut remaining = duration.abs();
+        if remaining.is_zero() {
+            return Ok(start.clone());
+        }
+        let step = if forwards { 1.day() } else { (-1).day() };
+        for date in start.date().series(step) {
+            let Some((open, close)) = self.window_on(calendar, date, start.time_zone())? else {
+                continue;
+            };
+            if forwards {
+                let from = open.max(start.clone());
+                if from >= close {
+                    continue;
+                }
+                let available = from.duration_until(&close);
+                if remaining <= available {
+                    return from.checked_add(remaining).map_err(to_error);
+                }
+                remaining -= available;
+            } else {
+                let to = close.min(start.clone());
+                if to <= open {
+                    continue;
+                }
+                let available = open.duration_until(&to);
+                if remaining <= available {
+                    return to.checked_sub(remaining).map_err(to_error);
+                }
+                remaining -= available;
+            }
+        }
+        Err(LabeledError::new(
+            "Ran out of dates while adding working time".to_string(),
+        ))
+    }
+}
+
+// Parse working hours like 09:00-17:30
+pub fn get_working_hours_from_string(hours: Spanned<String>) -> Result<WorkingHours, LabeledError> {
+    let error = |msg: String| {
+        LabeledError::new(format!("Invalid working hours: {msg}")).with_label(
+            "expected opening and closing times like 09:00-17:30",
+            hours.span,
+        )
+    };
+    let Some((open, close)) = hours.item.split_once('-') else {
+        return Err(error(hours.item.clone()));
+    };
+    let parse_time = |time: &str| {
+        time.trim()
+            .parse::<civil::Time>()
+            .map_err(|err| error(err.to_string()))
+    };
+    let working_hours = WorkingHours {
+        open: parse_time(open)?,
+        close: parse_time(close)?,
+    };
+    if working_hours.open >= working_hours.close {
+        return Err(error("closing time must be after opening time".to_string()));
+    }
+    Ok(working_hours)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+    use nu_protocol::Span;
+
+    fn spanned_hours(hours: &str) -> Spanned<String> {
+        Spanned {
+            item: hours.to_string(),
+            span: Span::unknown(),
+        }
+    }
+
+    fn zoned(datetime: &str) -> Zoned {
+        datetime.parse().unwrap()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtWorkTime)?;
+        plugin_test.test_command_examples(&DtAddWorkTime)
+    }
+
+    #[test]
+    fn test_work_time_negative_and_holidays() {
+        let calendar = BusinessCalendar::new(
+            vec![civil::Weekday::Saturday, civil::Weekday::Sunday],
+            vec![date(2024, 8, 26)],
+        )
+        .unwrap();
+        let hours = WorkingHours::default();
+        let start = zoned("2024-08-23T16:00:00-05:00[America/Chicago]");
+        let end = zoned("2024-08-27T10:00:00-05:00[America/Chicago]");
+        assert_eq!(
+            hours.work_time_between(&calendar, &start, &end).unwrap(),
+            SignedDuration::from_hours(2)
+        );
+        assert_eq!(
+            hours.work_time_between(&calendar, &end, &start).unwrap(),
+            SignedDuration::from_hours(-2)
+        );
+    }
+
+    #[test]
+    fn test_add_work_time_backwards() {
+        let calendar = BusinessCalendar::default();
+        let hours = WorkingHours::default();
+        let start = zoned("2024-08-26T10:00:00-05:00[America/Chicago]");
+        assert_eq!(
+            hours
+                .add_work_time(&calendar, &start, SignedDuration::from_hours(-3))
+                .unwrap()
+                .to_string(),
+            "2024-08-23T15:00:00-05:00[America/Chicago]"
+        );
+    }
+
+    #[test]
+    fn test_add_work_time_ending_at_close() {
+        let calendar = BusinessCalendar::default();
+        let hours = WorkingHours::default();
+        let start = zoned("2024-08-23T16:00:00-05:00[America/Chicago]");
+        assert_eq!(
+            hours
+                .add_work_time(&calendar, &start, SignedDuration::from_hours(1))
+                .unwrap()
+                .to_string(),
+            "2024-08-23T17:00:00-05:00[America/Chicago]"
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let calendar = BusinessCalendar::default();
+        let hours = WorkingHours::default();
+        let contains = |datetime: &str| hours.contains(&calendar, &zoned(datetime)).unwrap();
+        assert!(contains("2024-08-23T09:00:00-05:00[America/Chicago]"));
+        assert!(!contains("2024-08-23T17:00:00-05:00[America/Chicago]"));
+        assert!(!contains("2024-08-24T12:00:00-05:00[America/Chicago]"));
+    }
+
+    #[test]
+    fn test_invalid_working_hours() {
+        assert!(get_working_hours_from_string(spanned_hours("17:00-09:00")).is_err());
+        assert!(get_working_hours_from_string(spanned_hours("9am")).is_err());
+    }
+}
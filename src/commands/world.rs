@@ -0,0 +1,207 @@
+use super::business_days::{business_calendar_flags, get_business_calendar_from_call};
+use super::timezones::format_utc_offset;
+use super::tzdb::{local_now, local_time_zone};
+use super::utils::{get_plugin_config_strings, get_time_zone_from_string, get_zoned_from_value};
+use super::work_time::{WorkingHours, get_working_hours_from_string};
+use crate::DtPlugin;
+use jiff::Zoned;
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+
+// Plugin config key with the zones `dt world` shows when none are given
+const FAVORITE_ZONES_KEY: &str = "favorite_zones";
+
+pub struct DtWorld;
+
+impl SimplePluginCommand for DtWorld {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt world"
+    }
+
+    fn signature(&self) -> Signature {
+        business_calendar_flags(
+            Signature::build(self.name())
+                .rest(
+                    "zones",
+                    SyntaxShape::String,
+                    "Time zones to show, defaulting to favorite_zones in the plugin config.",
+                )
+                .named(
+                    "hours",
+                    SyntaxShape::String,
+                    "Working hours on business days like 09:00-17:30 (default 09:00-17:00).",
+                    Some('r'),
+                ),
+        )
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Show the piped in datetime, or now, in several time zones like a world clock"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Each row has the local datetime, offset and abbreviation in the zone, how many days its local date is ahead of or behind the piped in datetime's date, and whether it's within working hours on a business day there. Without zones the list in $env.config.plugins.dt.favorite_zones is used, e.g. $env.config.plugins.dt = { favorite_zones: [America/Chicago Europe/Berlin Asia/Kolkata] }."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "world clock",
+            "time zone",
+            "tz",
+            "zones",
+            "team",
+            "business hours",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "'2024-08-23T14:00:00+00:00[UTC]' | dt world America/Los_Angeles Europe/Berlin Pacific/Auckland",
+                description: "Show a datetime in three time zones",
+                result: Some(Value::test_list(vec![
+                    Value::test_record(record! {
+                        "zone" => Value::test_string("America/Los_Angeles"),
+                        "datetime" => Value::test_string("2024-08-23T07:00:00-07:00[America/Los_Angeles]"),
+                        "offset" => Value::test_string("-07:00"),
+                        "abbreviation" => Value::test_string("PDT"),
+                        "day" => Value::test_string("same day"),
+                        "business_hours" => Value::test_bool(false),
+                    }),
+                    Value::test_record(record! {
+                        "zone" => Value::test_string("Europe/Berlin"),
+                        "datetime" => Value::test_string("2024-08-23T16:00:00+02:00[Europe/Berlin]"),
+                        "offset" => Value::test_string("+02:00"),
+                        "abbreviation" => Value::test_string("CEST"),
+                        "day" => Value::test_string("same day"),
+                        "business_hours" => Value::test_bool(true),
+                    }),
+                    Value::test_record(record! {
+                        "zone" => Value::test_string("Pacific/Auckland"),
+                        "datetime" => Value::test_string("2024-08-24T02:00:00+12:00[Pacific/Auckland]"),
+                        "offset" => Value::test_string("+12:00"),
+                        "abbreviation" => Value::test_string("NZST"),
+                        "day" => Value::test_string("+1 day"),
+                        "business_hours" => Value::test_bool(false),
+                    }),
+                ])),
+            },
+            Example {
+                example: "'2024-08-23T14:00:00+00:00[UTC]' | dt world America/Los_Angeles --hours 07:00-15:00",
+                description: "Check business hours with an earlier working day",
+                result: Some(Value::test_list(vec![Value::test_record(record! {
+                    "zone" => Value::test_string("America/Los_Angeles"),
+                    "datetime" => Value::test_string("2024-08-23T07:00:00-07:00[America/Los_Angeles]"),
+                    "offset" => Value::test_string("-07:00"),
+                    "abbreviation" => Value::test_string("PDT"),
+                    "day" => Value::test_string("same day"),
+                    "business_hours" => Value::test_bool(true),
+                })])),
+            },
+            Example {
+                example: "$env.config.plugins.dt = { favorite_zones: [America/Chicago Europe/Berlin Asia/Kolkata] }; dt world",
+                description: "Show now in the favorite zones from the plugin config",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let mut zones: Vec<Spanned<String>> = call.rest(0)?;
+        if zones.is_empty() {
+            zones = get_plugin_config_strings(engine, FAVORITE_ZONES_KEY)?.unwrap_or_default();
+        }
+        if zones.is_empty() {
+            return Err(LabeledError::new("No time zones to show")
+                .with_label("give time zones to show", call.head)
+                .with_help(format!(
+                    "pass zones like `dt world America/Chicago Europe/Berlin` or set $env.config.plugins.dt.{FAVORITE_ZONES_KEY}"
+                )));
+        }
+        let hours = match call.get_flag("hours")? {
+            Some(hours) => get_working_hours_from_string(hours)?,
+            None => WorkingHours::default(),
+        };
+        let calendar = get_business_calendar_from_call(engine, call)?;
+        let datetime = match input {
+            Value::Nothing { .. } => local_now(engine),
+            _ => get_zoned_from_value(input, &local_time_zone(engine))?,
+        };
+
+        let rows = zones
+            .into_iter()
+            .map(|zone| {
+                let name = zone.item.clone();
+                let local = datetime.with_time_zone(get_time_zone_from_string(engine, zone)?);
+                let info = local.time_zone().to_offset_info(local.timestamp());
+                Ok(Value::record(
+                    record! {
+                        "zone" => Value::string(name, call.head),
+                        "datetime" => Value::string(local.to_string(), call.head),
+                        "offset" => Value::string(format_utc_offset(info.offset()), call.head),
+                        "abbreviation" => Value::string(info.abbreviation(), call.head),
+                        "day" => Value::string(
+                            format_day_delta(day_delta(&datetime, &local)),
+                            call.head,
+                        ),
+                        "business_hours" => Value::bool(hours.contains(&calendar, &local)?, call.head),
+                    },
+                    call.head,
+                ))
+            })
+            .collect::<Result<Vec<Value>, LabeledError>>()?;
+        Ok(Value::list(rows, call.head))
+    }
+}
+
+// Days from the reference datetime's local date to the other datetime's local date
+fn day_delta(reference: &Zoned, other: &Zoned) -> i32 {
+    (other.date() - reference.date()).get_days()
+}
+
+fn format_day_delta(days: i32) -> String {
+    match days {
+        0 => "same day".to_string(),
+        1 | -1 => format!("{days:+} day"),
+        _ => format!("{days:+} days"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nu_plugin_test_support::PluginTest;
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtWorld)
+    }
+
+    #[test]
+    fn test_day_delta() {
+        let reference: Zoned = "2024-08-23T23:30:00-05:00[America/Chicago]"
+            .parse()
+            .unwrap();
+        let tokyo = reference.in_tz("Asia/Tokyo").unwrap();
+        let honolulu = "2024-08-22T20:00:00-10:00[Pacific/Honolulu]"
+            .parse()
+            .unwrap();
+        assert_eq!(day_delta(&reference, &reference), 0);
+        assert_eq!(day_delta(&reference, &tokyo), 1);
+        assert_eq!(day_delta(&reference, &honolulu), -1);
+        assert_eq!(format_day_delta(0), "same day");
+        assert_eq!(format_day_delta(1), "+1 day");
+        assert_eq!(format_day_delta(-2), "-2 days");
+    }
+}
@@ -0,0 +1,397 @@
+use super::business_days::{
+    BusinessCalendar, business_calendar_flags, get_business_calendar_from_call,
+};
+use super::tzdb::local_time_zone;
+use super::utils::get_zoned_from_value;
+use crate::DtPlugin;
+use jiff::{ToSpan, civil};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value};
+
+pub struct DtYearFrac;
+
+impl SimplePluginCommand for DtYearFrac {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt yearfrac"
+    }
+
+    fn signature(&self) -> Signature {
+        business_calendar_flags(
+            Signature::build(self.name())
+                .required(
+                    "start",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date the accrual period starts on.",
+                )
+                .required(
+                    "end",
+                    SyntaxShape::OneOf(vec![SyntaxShape::String, SyntaxShape::DateTime]),
+                    "Date the accrual period ends on.",
+                )
+                .named(
+                    "basis",
+                    SyntaxShape::String,
+                    "Day count basis: 30/360 (default), 30e/360, 30e/360-isda, act/360, act/365f, act/act-isda, act/act-icma, act/act-excel or bus/252. Excel's basis numbers 0-4 also work.",
+                    Some('b'),
+                ),
+        )
+        .named(
+            "frequency",
+            SyntaxShape::Int,
+            "Coupons per year for act/act-icma: 1 (default), 2, 3, 4, 6 or 12.",
+            Some('f'),
+        )
+        .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Return the fraction of a year between two dates using a financial day count convention"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Only the dates are used, times of day are ignored. When the end is before the start the dates are swapped, like Excel's YEARFRAC, so the result is never negative.
+
+30/360 is the US (NASD) bond basis Excel uses for basis 0, with its end of February rules. act/act-excel is Excel's basis 1, which averages the year lengths the period touches. 30e/360-isda treats the end date as the termination date so a last day of February end isn't moved to the 30th. act/act-icma treats the end date as a regular coupon date and counts back whole coupon periods from it. bus/252 counts business days with --weekend, --holidays and --calendar."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "date",
+            "finance",
+            "day count",
+            "accrual",
+            "interest",
+            "excel",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt yearfrac 2012-01-01 2012-07-30",
+                description: "Return the year fraction using the 30/360 US basis like Excel's YEARFRAC default",
+                result: Some(Value::test_float(209.0 / 360.0)),
+            },
+            Example {
+                example: "dt yearfrac 2012-07-30 2012-01-01",
+                description: "Return the same positive year fraction when the dates are reversed",
+                result: Some(Value::test_float(209.0 / 360.0)),
+            },
+            Example {
+                example: "dt yearfrac 2003-11-01 2004-05-01 --basis act/act-isda",
+                description: "Return the ISDA actual/actual year fraction across a year end",
+                result: Some(Value::test_float(61.0 / 365.0 + 121.0 / 366.0)),
+            },
+            Example {
+                example: "dt yearfrac 2003-11-01 2004-05-01 --basis act/act-icma --frequency 2",
+                description: "Return the ICMA actual/actual year fraction of a semiannual coupon period",
+                result: Some(Value::test_float(0.5)),
+            },
+            Example {
+                example: "dt yearfrac 2024-01-02 2024-01-09 --basis bus/252",
+                description: "Return the business day year fraction used in Brazil",
+                result: Some(Value::test_float(5.0 / 252.0)),
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        _input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let start_value: Value = call.req(0)?;
+        let end_value: Value = call.req(1)?;
+        let basis = match call.get_flag("basis")? {
+            Some(basis) => get_day_count_basis_from_string(basis)?,
+            None => DayCountBasis::Thirty360Us,
+        };
+        let frequency: Option<Spanned<i64>> = call.get_flag("frequency")?;
+        let frequency = match frequency {
+            Some(frequency) if ![1, 2, 3, 4, 6, 12].contains(&frequency.item) => {
+                return Err(
+                    LabeledError::new("frequency must divide evenly into 12 months")
+                        .with_label(format!("got {}", frequency.item), frequency.span),
+                );
+            }
+            Some(frequency) => frequency.item as i32,
+            None => 1,
+        };
+        let calendar = get_business_calendar_from_call(engine, call)?;
+
+        let start = get_zoned_from_value(&start_value, &local_tz)?.date();
+        let end = get_zoned_from_value(&end_value, &local_tz)?.date();
+        let fraction = year_fraction(start, end, basis, frequency, &calendar)?;
+        Ok(Value::float(fraction, call.head))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayCountBasis {
+    Thirty360Us,
+    Thirty360European,
+    Thirty360Isda,
+    Actual360,
+    Actual365Fixed,
+    ActualActualIsda,
+    ActualActualIcma,
+    ActualActualExcel,
+    Business252,
+}
+
+pub fn get_day_count_basis_from_string(
+    basis: Spanned<String>,
+) -> Result<DayCountBasis, LabeledError> {
+    match basis.item.to_lowercase().as_ref() {
+        "30/360" | "30/360-us" | "30u/360" | "bond" | "0" => Ok(DayCountBasis::Thirty360Us),
+        "30e/360" | "eurobond" | "4" => Ok(DayCountBasis::Thirty360European),
+        "30e/360-isda" | "30e/360 isda" => Ok(DayCountBasis::Thirty360Isda),
+        "act/360" | "actual/360" | "2" => Ok(DayCountBasis::Actual360),
+        "act/365f" | "act/365" | "actual/365" | "3" => Ok(DayCountBasis::Actual365Fixed),
+        "act/act-isda" | "act/act" | "actual/actual" => Ok(DayCountBasis::ActualActualIsda),
+        "act/act-icma" | "act/act-isma" => Ok(DayCountBasis::ActualActualIcma),
+        "act/act-excel" | "1" => Ok(DayCountBasis::ActualActualExcel),
+        "bus/252" => Ok(DayCountBasis::Business252),
+        _ => Err(
+            LabeledError::new(format!("Invalid day count basis: {}", basis.item)).with_label(
+                "expected one of 30/360, 30e/360, 30e/360-isda, act/360, act/365f, act/act-isda, act/act-icma, act/act-excel or bus/252",
+                basis.span,
+            ),
+        ),
+    }
+}
+
+pub fn year_fraction(
+    start: civil::Date,
+    end: civil::Date,
+    basis: DayCountBasis,
+    frequency: i32,
+    calendar: &BusinessCalendar,
+) -> Result<f64, LabeledError> {
+    // Excel swaps reversed dates rather than returning a negative fraction
+    let (start, end) = if end < start {
+        (end, start)
+    } else {
+        (start, end)
+    };
+    let fraction = match basis {
+        DayCountBasis::Thirty360Us
+        | DayCountBasis::Thirty360European
+        | DayCountBasis::Thirty360Isda => thirty_360_days(start, end, basis) as f64 / 360.0,
+        DayCountBasis::Actual360 => days_between(start, end)? as f64 / 360.0,
+        DayCountBasis::Actual365Fixed => days_between(start, end)? as f64 / 365.0,
+        DayCountBasis::ActualActualIsda => {
+            // Each calendar year's days are divided by that year's length
+            let mut fraction = 0.0;
+            let mut from = start;
+            while from < end {
+                let next_year = civil::Date::new(from.year() + 1, 1, 1)
+                    .map_err(|err| LabeledError::new(err.to_string()))?;
+                let to = next_year.min(end);
+                fraction += days_between(from, to)? as f64 / f64::from(from.days_in_year());
+                from = to;
+            }
+            fraction
+        }
+        DayCountBasis::ActualActualIcma => {
+            // Count back whole coupon periods from the end, the period containing the
+            // start contributes its share of 1 / frequency
+            let months = 12 / frequency;
+            let mut period_end = end;
+            let mut periods = 0;
+            loop {
+                let period_start = end
+                    .checked_sub(((periods + 1) * months).months())
+                    .map_err(|err| LabeledError::new(err.to_string()))?;
+                if period_start <= start {
+                    let partial = days_between(start, period_end)? as f64
+                        / (f64::from(frequency) * days_between(period_start, period_end)? as f64);
+                    break f64::from(periods) / f64::from(frequency) + partial;
+                }
+                period_end = period_start;
+                periods += 1;
+            }
+        }
+        DayCountBasis::ActualActualExcel => {
+            let days = days_between(start, end)? as f64;
+            let one_year_later = start
+                .checked_add(1.year())
+                .map_err(|err| LabeledError::new(err.to_string()))?;
+            let year_length = if end <= one_year_later {
+                if start.year() == end.year() {
+                    f64::from(start.days_in_year())
+                } else if contains_leap_day(start, end) {
+                    366.0
+                } else {
+                    365.0
+                }
+            } else {
+                let first = civil::Date::new(start.year(), 1, 1)
+                    .map_err(|err| LabeledError::new(err.to_string()))?;
+                let last = civil::Date::new(end.year() + 1, 1, 1)
+                    .map_err(|err| LabeledError::new(err.to_string()))?;
+                days_between(first, last)? as f64 / f64::from(end.year() - start.year() + 1)
+            };
+            days / year_length
+        }
+        DayCountBasis::Business252 => calendar.count_business_days(start, end) as f64 / 252.0,
+    };
+    Ok(fraction)
+}
+
+fn days_between(start: civil::Date, end: civil::Date) -> Result<i32, LabeledError> {
+    end.since(start)
+        .map(|span| span.get_days())
+        .map_err(|err| LabeledError::new(err.to_string()))
+}
+
+// Whether a February 29th falls between two dates, inclusive
+fn contains_leap_day(start: civil::Date, end: civil::Date) -> bool {
+    (start.year()..=end.year()).any(|year| {
+        civil::Date::new(year, 2, 29).is_ok_and(|leap_day| start <= leap_day && leap_day <= end)
+    })
+}
+
+fn thirty_360_days(start: civil::Date, end: civil::Date, basis: DayCountBasis) -> i64 {
+    let is_last_of_february =
+        |date: civil::Date| date.month() == 2 && date.day() == date.days_in_month();
+    let (mut d1, mut d2) = (i64::from(start.day()), i64::from(end.day()));
+    match basis {
+        DayCountBasis::Thirty360Us => {
+            if is_last_of_february(start) && is_last_of_february(end) {
+                d2 = 30;
+            }
+            if is_last_of_february(start) {
+                d1 = 30;
+            }
+            if d2 == 31 && d1 >= 30 {
+                d2 = 30;
+            }
+            if d1 == 31 {
+                d1 = 30;
+            }
+        }
+        DayCountBasis::Thirty360Isda => {
+            if start.day() == start.days_in_month() {
+                d1 = 30;
+            }
+            // The end is the termination date, which keeps a February month end
+            if end.day() == end.days_in_month() && end.month() != 2 {
+                d2 = 30;
+            }
+        }
+        _ => {
+            d1 = d1.min(30);
+            d2 = d2.min(30);
+        }
+    }
+    360 * i64::from(end.year() - start.year())
+        + 30 * i64::from(end.month() - start.month())
+        + (d2 - d1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+    use nu_plugin_test_support::PluginTest;
+
+    fn fraction(start: civil::Date, end: civil::Date, basis: DayCountBasis) -> f64 {
+        year_fraction(start, end, basis, 1, &BusinessCalendar::default()).unwrap()
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        PluginTest::new("dt", DtPlugin.into())?.test_command_examples(&DtYearFrac)
+    }
+
+    #[test]
+    fn test_yearfrac_excel_bases() {
+        let (start, end) = (date(2012, 1, 1), date(2012, 7, 30));
+        assert_eq!(
+            fraction(start, end, DayCountBasis::ActualActualExcel),
+            211.0 / 366.0
+        );
+        assert_eq!(
+            fraction(start, end, DayCountBasis::Actual360),
+            211.0 / 360.0
+        );
+        assert_eq!(
+            fraction(start, end, DayCountBasis::Actual365Fixed),
+            211.0 / 365.0
+        );
+        // More than a year averages the lengths of 2011, 2012 and 2013
+        assert_eq!(
+            fraction(
+                date(2011, 6, 1),
+                date(2013, 6, 1),
+                DayCountBasis::ActualActualExcel
+            ),
+            731.0 / (1096.0 / 3.0)
+        );
+    }
+
+    #[test]
+    fn test_yearfrac_thirty_360_variants() {
+        let (start, end) = (date(2006, 8, 31), date(2007, 2, 28));
+        assert_eq!(
+            fraction(start, end, DayCountBasis::Thirty360European),
+            178.0 / 360.0
+        );
+        let (start, end) = (date(2007, 2, 28), date(2007, 8, 31));
+        assert_eq!(
+            fraction(start, end, DayCountBasis::Thirty360Isda),
+            180.0 / 360.0
+        );
+        assert_eq!(
+            fraction(start, end, DayCountBasis::Thirty360European),
+            182.0 / 360.0
+        );
+        assert_eq!(
+            fraction(start, end, DayCountBasis::Thirty360Us),
+            180.0 / 360.0
+        );
+    }
+
+    #[test]
+    fn test_yearfrac_icma_with_stub() {
+        // A long first period of 1.5 semiannual coupons
+        let result = year_fraction(
+            date(2003, 2, 1),
+            date(2003, 11, 1),
+            DayCountBasis::ActualActualIcma,
+            2,
+            &BusinessCalendar::default(),
+        )
+        .unwrap();
+        assert_eq!(result, 0.5 + 89.0 / (2.0 * 181.0));
+    }
+
+    #[test]
+    fn test_yearfrac_reversed_dates_are_swapped() {
+        assert_eq!(
+            fraction(
+                date(2012, 7, 30),
+                date(2012, 1, 1),
+                DayCountBasis::Actual360
+            ),
+            211.0 / 360.0
+        );
+        for basis in [
+            DayCountBasis::Thirty360Us,
+            DayCountBasis::Thirty360European,
+            DayCountBasis::ActualActualIsda,
+            DayCountBasis::ActualActualExcel,
+        ] {
+            assert_eq!(
+                fraction(date(2013, 2, 28), date(2011, 1, 15), basis),
+                fraction(date(2011, 1, 15), date(2013, 2, 28), basis)
+            );
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use super::timezones::{
+    available_time_zone_names, format_utc_offset, get_time_zone_links_for_canonical,
+};
+use super::tzdb::{local_time_zone, time_zone_database};
+use super::utils::{get_weekday_from_string, get_zoned_from_value};
+use crate::DtPlugin;
+use jiff::{SignedDuration, Timestamp, Zoned, civil, civil::Weekday};
+use nu_plugin::{EngineInterface, EvaluatedCall, SimplePluginCommand};
+use nu_protocol::{
+    Category, Example, LabeledError, Signature, Spanned, SyntaxShape, Value, record,
+};
+use std::collections::BTreeMap;
+
+pub struct DtZonesAt;
+
+impl SimplePluginCommand for DtZonesAt {
+    type Plugin = DtPlugin;
+
+    fn name(&self) -> &str {
+        "dt zones-at"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(self.name())
+            .named(
+                "time",
+                SyntaxShape::String,
+                "Local time window like 17:00-18:30 or 22:00-02:00, or a time like 17:00 for the hour starting then.",
+                Some('t'),
+            )
+            .named(
+                "weekday",
+                SyntaxShape::List(Box::new(SyntaxShape::String)),
+                "Local weekdays to match, e.g. [mon] or [sat sun].",
+                Some('w'),
+            )
+            .switch(
+                "canonical",
+                "Only list canonical zones, leaving out aliases like US/Central",
+                Some('c'),
+            )
+            .category(Category::Date)
+    }
+
+    fn description(&self) -> &str {
+        "Find the IANA time zones whose local time matches a time window or weekday at the piped in datetime or now, grouped by offset"
+    }
+
+    fn extra_description(&self) -> &str {
+        r#"Each row is an offset with the local datetime and weekday there and the zones at that offset. Time windows include their start and exclude their end, and a window whose end is before its start wraps past midnight. Without --time or --weekday every zone is listed."#
+    }
+
+    fn search_terms(&self) -> Vec<&str> {
+        vec![
+            "time zone",
+            "where",
+            "follow the sun",
+            "local time",
+            "offset",
+            "weekday",
+        ]
+    }
+
+    fn examples(&self) -> Vec<Example<'_>> {
+        vec![
+            Example {
+                example: "dt zones-at --time 17:00",
+                description: "Find where it's currently between 17:00 and 18:00",
+                result: None,
+            },
+            Example {
+                example: "'2024-08-25T20:00:00+00:00[UTC]' | dt zones-at --weekday [mon] --canonical",
+                description: "Find where it's already Monday on a Sunday evening in UTC",
+                result: None,
+            },
+            Example {
+                example: "dt zones-at --time 09:00-17:00 --weekday [mon tue wed thu fri] | get zones | flatten",
+                description: "List the zones currently within working hours on a weekday",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        _plugin: &DtPlugin,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: &Value,
+    ) -> Result<Value, LabeledError> {
+        let local_tz = local_time_zone(engine);
+        let time_window = call
+            .get_flag("time")?
+            .map(get_local_time_window_from_string)
+            .transpose()?;
+        let weekdays = call
+            .get_flag::<Vec<Spanned<String>>>("weekday")?
+            .map(|weekdays| {
+                weekdays
+                    .into_iter()
+                    .map(get_weekday_from_string)
+                    .collect::<Result<Vec<Weekday>, LabeledError>>()
+            })
+            .transpose()?;
+        let canonical_only = call.has_flag("canonical")?;
+        let timestamp = match input {
+            Value::Nothing { .. } => Timestamp::now(),
+            _ => get_zoned_from_value(input, &local_tz)?.timestamp(),
+        };
+
+        let links = if canonical_only {
+            Some(get_time_zone_links_for_canonical(call.head)?)
+        } else {
+            None
+        };
+        let condition = LocalCondition {
+            time_window,
+            weekdays,
+        };
+        let mut groups: BTreeMap<i32, (Zoned, Vec<String>)> = BTreeMap::new();
+        for name in available_time_zone_names() {
+            if links
+                .as_ref()
+                .is_some_and(|links| links.contains_key(&name))
+            {
+                continue;
+            }
+            let Ok(time_zone) = time_zone_database().get(&name) else {
+                continue;
+            };
+            let local = timestamp.to_zoned(time_zone);
+            if !condition.matches(&local) {
+                continue;
+            }
+            groups
+                .entry(local.offset().seconds())
+                .or_insert_with(|| (local.clone(), vec![]))
+                .1
+                .push(name);
+        }
+
+        let rows = groups
+            .into_values()
+            .map(|(local, zones)| {
+                Value::record(
+                    record! {
+                        "offset" => Value::string(format_utc_offset(local.offset()), call.head),
+                        "datetime" => Value::string(local.datetime().to_string(), call.head),
+                        "weekday" => Value::string(local.strftime("%A").to_string(), call.head),
+                        "zones" => Value::list(
+                            zones
+                                .into_iter()
+                                .map(|zone| Value::string(zone, call.head))
+                                .collect(),
+                            call.head,
+                        ),
+                    },
+                    call.head,
+                )
+            })
+            .collect();
+        Ok(Value::list(rows, call.head))
+    }
+}
+
+// What a zone's local civil time must satisfy to be listed
+struct LocalCondition {
+    time_window: Option<(civil::Time, civil::Time)>,
+    weekdays: Option<Vec<Weekday>>,
+}
+
+impl LocalCondition {
+    fn matches(&self, local: &Zoned) -> bool {
+        let time = local.time();
+        let in_window = match self.time_window {
+            Some((start, end)) if start < end => start <= time && time < end,
+            Some((start, end)) => start <= time || time < end,
+            None => true,
+        };
+        let on_weekday = match &self.weekdays {
+            Some(weekdays) => weekdays.contains(&local.weekday()),
+            None => true,
+        };
+        in_window && on_weekday
+    }
+}
+
+// Parse a local time window like 17:00-18:30, or a single time for the hour
+// starting then
+fn get_local_time_window_from_string(
+    window: Spanned<String>,
+) -> Result<(civil::Time, civil::Time), LabeledError> {
+    let parse_time = |time: &str| {
+        time.trim().parse::<civil::Time>().map_err(|err| {
+            LabeledError::new(format!("Invalid time window: {}", window.item)).with_label(
+                format!("expected a time like 17:00 or a window like 17:00-18:30: {err}"),
+                window.span,
+            )
+        })
+    };
+    match window.item.split_once('-') {
+        Some((start, end)) => Ok((parse_time(start)?, parse_time(end)?)),
+        None => {
+            let start = parse_time(&window.item)?;
+            Ok((start, start.wrapping_add(SignedDuration::from_hours(1))))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::time;
+    use nu_protocol::Span;
+
+    fn window(text: &str) -> (civil::Time, civil::Time) {
+        get_local_time_window_from_string(Spanned {
+            item: text.to_string(),
+            span: Span::unknown(),
+        })
+        .unwrap()
+    }
+
+    fn zoned(datetime: &str) -> Zoned {
+        datetime.parse().unwrap()
+    }
+
+    #[test]
+    fn test_get_local_time_window_from_string() {
+        assert_eq!(window("17:00"), (time(17, 0, 0, 0), time(18, 0, 0, 0)));
+        assert_eq!(window("23:30"), (time(23, 30, 0, 0), time(0, 30, 0, 0)));
+        assert_eq!(window("22:00-02:00"), (time(22, 0, 0, 0), time(2, 0, 0, 0)));
+        assert!(
+            get_local_time_window_from_string(Spanned {
+                item: "5pm".to_string(),
+                span: Span::unknown(),
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_local_condition() {
+        let condition = LocalCondition {
+            time_window: Some(window("22:00-02:00")),
+            weekdays: Some(vec![Weekday::Monday]),
+        };
+        assert!(condition.matches(&zoned("2024-08-26T01:30:00+09:00[Asia/Tokyo]")));
+        assert!(condition.matches(&zoned("2024-08-26T23:00:00+09:00[Asia/Tokyo]")));
+        assert!(!condition.matches(&zoned("2024-08-26T02:00:00+09:00[Asia/Tokyo]")));
+        assert!(!condition.matches(&zoned("2024-08-25T23:00:00+09:00[Asia/Tokyo]")));
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), nu_protocol::ShellError> {
+        use nu_plugin_test_support::PluginTest;
+
+        let mut plugin_test = PluginTest::new("dt", DtPlugin.into())?;
+        plugin_test.test_command_examples(&DtZonesAt)?;
+        // 20:00 UTC on a Sunday is already Monday from UTC+04:00 eastwards
+        let monday = plugin_test
+            .eval("'2024-08-25T20:00:00+00:00[UTC]' | dt zones-at --weekday [mon] --time 01:00")?;
+        let rows = monday.into_value(Span::test_data())?.into_list()?;
+        let nepal = rows
+            .iter()
+            .find(|row| row.get_data_by_key("offset") == Some(Value::test_string("+05:45")))
+            .expect("Nepal is at +05:45");
+        assert_eq!(
+            nepal.get_data_by_key("datetime"),
+            Some(Value::test_string("2024-08-26T01:45:00"))
+        );
+        assert!(
+            rows.iter()
+                .all(|row| row.get_data_by_key("weekday") == Some(Value::test_string("Monday")))
+        );
+        Ok(())
+    }
+}
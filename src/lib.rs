@@ -1,15 +1,48 @@
+// Tests return nu_protocol::ShellError, which is bigger than clippy likes
+#![cfg_attr(test, allow(clippy::result_large_err))]
+
 mod commands;
 
 use commands::Dt;
 use nu_plugin::{Plugin, PluginCommand};
 
 pub use commands::DtAdd;
+pub use commands::DtAddBusinessDays;
+pub use commands::DtAddWorkTime;
+pub use commands::DtCalendarEvent;
+pub use commands::DtCheck;
+pub use commands::DtCronNext;
+pub use commands::DtCronPrev;
 pub use commands::DtDiff;
+pub use commands::DtEaster;
+pub use commands::DtEndOf;
+pub use commands::DtFiscalPeriod;
 pub use commands::DtFormat;
+pub use commands::DtGuessZone;
+pub use commands::DtHolidays;
+pub use commands::DtIsHoliday;
+pub use commands::DtListTimezones;
+pub use commands::DtNext;
 pub use commands::DtNow;
+pub use commands::DtNthWeekday;
+pub use commands::DtOverlapHours;
 pub use commands::DtPart;
+pub use commands::DtPrev;
+pub use commands::DtRound;
+pub use commands::DtRrule;
+pub use commands::DtSchedule;
+pub use commands::DtSeq;
+pub use commands::DtStartOf;
 pub use commands::DtTo;
+pub use commands::DtToTimezone;
+pub use commands::DtTruncate;
+pub use commands::DtTzTransitions;
+pub use commands::DtTzdb;
 pub use commands::DtUtcNow;
+pub use commands::DtWorkTime;
+pub use commands::DtWorld;
+pub use commands::DtYearFrac;
+pub use commands::DtZonesAt;
 
 pub struct DtPlugin;
 
@@ -31,6 +64,36 @@ impl Plugin for DtPlugin {
             Box::new(DtDiff),
             Box::new(DtFormat),
             Box::new(DtTo),
+            Box::new(DtRound),
+            Box::new(DtTruncate),
+            Box::new(DtStartOf),
+            Box::new(DtEndOf),
+            Box::new(DtSeq),
+            Box::new(DtRrule),
+            Box::new(DtCronNext),
+            Box::new(DtCronPrev),
+            Box::new(DtCalendarEvent),
+            Box::new(DtAddBusinessDays),
+            Box::new(DtHolidays),
+            Box::new(DtIsHoliday),
+            Box::new(DtEaster),
+            Box::new(DtWorkTime),
+            Box::new(DtAddWorkTime),
+            Box::new(DtYearFrac),
+            Box::new(DtSchedule),
+            Box::new(DtNthWeekday),
+            Box::new(DtNext),
+            Box::new(DtPrev),
+            Box::new(DtFiscalPeriod),
+            Box::new(DtListTimezones),
+            Box::new(DtToTimezone),
+            Box::new(DtTzTransitions),
+            Box::new(DtTzdb),
+            Box::new(DtWorld),
+            Box::new(DtOverlapHours),
+            Box::new(DtCheck),
+            Box::new(DtZonesAt),
+            Box::new(DtGuessZone),
         ]
     }
 }